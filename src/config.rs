@@ -0,0 +1,52 @@
+use serde::Deserialize;
+use std::fs;
+use std::path::PathBuf;
+
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    /// Maximum time a single external command (mix, git, ...) may run
+    pub command_timeout_secs: u64,
+    /// Directory names to skip when scanning for projects, replacing the defaults
+    pub skip_dirs: Option<Vec<String>>,
+    /// Maximum directory depth scanned for projects
+    pub scan_depth: Option<usize>,
+    /// Glob patterns of paths to skip when scanning for projects
+    pub ignore_glob: Option<Vec<String>>,
+    /// The `[tools]` section
+    pub tools: ToolsConfig,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct ToolsConfig {
+    /// Tool names that are not registered at startup
+    pub disabled: Vec<String>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            command_timeout_secs: 300,
+            skip_dirs: None,
+            scan_depth: None,
+            ignore_glob: None,
+            tools: ToolsConfig::default(),
+        }
+    }
+}
+
+fn get_config_path() -> PathBuf {
+    dirs::home_dir()
+        .map(|h| h.join(".config/steve/config.toml"))
+        .unwrap_or_else(|| PathBuf::from("config.toml"))
+}
+
+/// Load `~/.config/steve/config.toml`, falling back to defaults when the file
+/// is missing or invalid.
+pub fn load_config() -> Config {
+    fs::read_to_string(get_config_path())
+        .ok()
+        .and_then(|contents| toml::from_str(&contents).ok())
+        .unwrap_or_default()
+}
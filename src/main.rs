@@ -1,17 +1,62 @@
+mod config;
+mod shutdown;
 mod tools;
 
+use clap::{Parser, ValueEnum};
+use config::load_config;
 use rmcp::{
-    ErrorData as McpError, ServerHandler, ServiceExt,
-    handler::server::{router::tool::ToolRouter, wrapper::Parameters},
-    model::{CallToolResult, Implementation, ProtocolVersion, ServerCapabilities, ServerInfo},
-    tool, tool_handler, tool_router,
+    ErrorData as McpError, RoleServer, ServerHandler, ServiceExt,
+    handler::server::{router::tool::ToolRouter, tool::ToolCallContext, wrapper::Parameters},
+    model::{
+        CallToolRequestParam, CallToolResult, Implementation, ListToolsResult,
+        PaginatedRequestParam, ProtocolVersion, ServerCapabilities, ServerInfo,
+    },
+    service::RequestContext,
+    tool, tool_router,
+    transport::streamable_http_server::{
+        StreamableHttpServerConfig, StreamableHttpService, session::local::LocalSessionManager,
+    },
 };
+use shutdown::{cancel_on_sigterm, shutdown_token};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::BufWriter;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 use tokio::io::{stdin, stdout};
-use tools::{ElixirProjectsRequest, SpotifyRequest, handle_elixir_projects, handle_spotify};
+use tools::{
+    AsdfRequest, AuditLogRequest, BookmarkRequest, BrightnessRequest, ClipboardHistoryRequest,
+    ColorRequest, CronRequest, DiffRequest, DisplayRequest, DockerRequest, ElixirProjectsRequest,
+    HexSearchRequest, HttpRequest, IexEvalRequest, JwtDecodeRequest, LaunchRequest, ManPageRequest,
+    MixNewRequest, MixTaskRequest, NoteRequest, PackageRequest, PasswordGenRequest, PhxNewRequest,
+    PortCheckRequest, ProjectScans, RegexTestRequest, ScreenshotRequest, SemverRequest,
+    SpotifyRequest, SshTestRequest, TemplateRequest, TimeRequest, TimerRequest, TldrRequest,
+    TmuxRequest, TodoRequest, ValidateRequest, VolumeRequest, WordCountRequest, call_error,
+    handle_asdf, handle_audit_log, handle_bookmark, handle_brightness, handle_clipboard_history,
+    handle_color_convert, handle_cpu_info, handle_cron, handle_diff_text, handle_displays,
+    handle_docker, handle_elixir_projects, handle_generate_password, handle_hex_search,
+    handle_http, handle_iex_eval, handle_journal_errors, handle_jwt_decode, handle_keybinding_list,
+    handle_last_error, handle_launch, handle_list_tools, handle_man_page, handle_memory_info,
+    handle_mise_current, handle_mix_new, handle_mix_task, handle_note, handle_package,
+    handle_phx_new, handle_port_check, handle_regex_test, handle_screenshot, handle_semver,
+    handle_spotify, handle_ssh_list_keys, handle_ssh_test, handle_temperature,
+    handle_template_render, handle_time_convert, handle_timer, handle_tldr, handle_tmux,
+    handle_todo, handle_uptime, handle_validate, handle_volume, handle_whoami, handle_word_count,
+    open_audit_log, start_project_watcher, write_audit_entry,
+};
 
 #[derive(Clone)]
 pub struct Steve {
     tool_router: ToolRouter<Self>,
+    /// Running stopwatches by label
+    stopwatches: Arc<Mutex<HashMap<String, Instant>>>,
+    /// Appends every tool call to ~/.cache/steve/audit.log
+    audit_log: Arc<Mutex<Option<BufWriter<File>>>>,
+    /// Project scans in flight, shared by identical elixir_projects calls
+    project_scans: Arc<ProjectScans>,
+    /// Tool name, request JSON and error of the last failed call
+    last_error: Arc<Mutex<Option<(String, String, String)>>>,
 }
 
 impl Default for Steve {
@@ -23,13 +68,24 @@ impl Default for Steve {
 #[tool_router]
 impl Steve {
     pub fn new() -> Self {
+        start_project_watcher();
+
+        let mut tool_router = Self::tool_router();
+        for name in &load_config().tools.disabled {
+            tool_router.remove_route(name);
+        }
+
         Self {
-            tool_router: Self::tool_router(),
+            tool_router,
+            stopwatches: Arc::default(),
+            audit_log: Arc::new(Mutex::new(open_audit_log())),
+            project_scans: Arc::default(),
+            last_error: Arc::default(),
         }
     }
 
     #[tool(
-        description = "Control Spotify playing in Firefox via MPRIS. Actions: play, pause, play_pause, next, previous, status"
+        description = "Control Spotify playing in Firefox via MPRIS. Actions: play, pause, play_pause, next, previous, status, volume_up, volume_down, volume_set, volume_get, seek, list_players"
     )]
     async fn spotify(
         &self,
@@ -39,17 +95,433 @@ impl Steve {
     }
 
     #[tool(
-        description = "Manage Elixir projects. Actions: list, update_deps, outdated, git_pull, git_push, git_status, refresh. Uses cached project list from ~/.cache/steve/projects. Use 'project' to filter by name."
+        description = "Manage Docker containers. Actions: ps, ps_all, logs, compose_status, compose_up, compose_down, compose_restart"
+    )]
+    async fn docker(
+        &self,
+        Parameters(req): Parameters<DockerRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        Ok(handle_docker(req).await)
+    }
+
+    #[tool(
+        description = "Manage Elixir projects. Actions: list, update_deps, outdated, git_pull, git_push, git_status, refresh, delete, ignore, unignore, watch, pre_commit_check, deps_tree, xref, ecto_reset, git_push_force_with_lease, git_commit, git_add, test, changelog_summary, version_bump, audit, coverage, import, export, git_fetch, rebase, load_test, health_check, docker_build, mise_check, nix_info, nix_build, deps_get, compile_warnings, stale, git_remote, summary, dep_graph, lock_diff, run_in_all, version_matrix, git_squash_local, coverage_badge, port_conflicts, archive_deps, check_env. Uses cached project list from ~/.cache/steve/projects. Use 'project' to filter by name."
     )]
     async fn elixir_projects(
         &self,
         Parameters(req): Parameters<ElixirProjectsRequest>,
     ) -> Result<CallToolResult, McpError> {
-        Ok(handle_elixir_projects(req).await)
+        Ok(handle_elixir_projects(req, &self.project_scans).await)
+    }
+
+    #[tool(
+        description = "Scaffold a new Elixir project with 'mix new'. Templates: app (default), sup, lib. Defaults to ~/src/flt and refreshes the project cache."
+    )]
+    async fn mix_new(
+        &self,
+        Parameters(req): Parameters<MixNewRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        Ok(handle_mix_new(req).await)
+    }
+
+    #[tool(
+        description = "Scaffold a new Phoenix application with 'mix phx.new --no-install'. Options: no_ecto, no_live, no_assets. Defaults to ~/src/flt and adds it to the project cache."
+    )]
+    async fn mix_phx_new(
+        &self,
+        Parameters(req): Parameters<PhxNewRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        Ok(handle_phx_new(req).await)
+    }
+
+    #[tool(
+        description = "Run any mix task in a cached project: 'mix <task> [args...]' with optional env vars and timeout. Returns stdout and stderr, truncated to 8KiB."
+    )]
+    async fn run_mix_task(
+        &self,
+        Parameters(req): Parameters<MixTaskRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        Ok(handle_mix_task(req).await)
+    }
+
+    #[tool(
+        description = "Evaluate an Elixir expression in a cached project with 'mix run --no-start -e' and return the inspected result. Max 500 characters, 30s timeout."
+    )]
+    async fn iex_eval(
+        &self,
+        Parameters(req): Parameters<IexEvalRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        Ok(handle_iex_eval(req).await)
+    }
+
+    #[tool(
+        description = "Search the Hex.pm registry with 'mix hex.search' and return package name, version, description and downloads (when Hex reports them). Default limit 10."
+    )]
+    async fn hex_search(
+        &self,
+        Parameters(req): Parameters<HexSearchRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        Ok(handle_hex_search(req).await)
+    }
+
+    #[tool(
+        description = "Check SSH connectivity to a host: TCP connect latency, server version banner, and whether the host is in ~/.ssh/known_hosts"
+    )]
+    async fn ssh_test(
+        &self,
+        Parameters(req): Parameters<SshTestRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        Ok(handle_ssh_test(req).await)
+    }
+
+    #[tool(
+        description = "List SSH public keys in ~/.ssh with algorithm, size, fingerprint and comment, plus keys loaded in the SSH agent"
+    )]
+    async fn ssh_list_keys(&self) -> Result<CallToolResult, McpError> {
+        Ok(handle_ssh_list_keys().await)
+    }
+
+    #[tool(
+        description = "Check whether TCP ports are open on a host (or an IPv4 CIDR range up to /24), with connection latency"
+    )]
+    async fn port_check(
+        &self,
+        Parameters(req): Parameters<PortCheckRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        Ok(handle_port_check(req).await)
+    }
+
+    #[tool(
+        description = "Make an HTTP request (GET, POST, PUT, PATCH, DELETE) with optional body and headers. Returns status, response headers and up to 8KiB of body."
+    )]
+    async fn http(
+        &self,
+        Parameters(req): Parameters<HttpRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        Ok(handle_http(req).await)
+    }
+
+    #[tool(description = "Show the current user, home directory, shell, uid and groups")]
+    async fn whoami(&self) -> Result<CallToolResult, McpError> {
+        Ok(handle_whoami())
+    }
+
+    #[tool(description = "Show system uptime and 1/5/15-minute load averages")]
+    async fn uptime(&self) -> Result<CallToolResult, McpError> {
+        Ok(handle_uptime())
+    }
+
+    #[tool(description = "Show memory and swap usage from /proc/meminfo")]
+    async fn memory_info(&self) -> Result<CallToolResult, McpError> {
+        Ok(handle_memory_info())
+    }
+
+    #[tool(
+        description = "Show CPU model, core count and utilization since the previous cpu_info call"
+    )]
+    async fn cpu_info(&self) -> Result<CallToolResult, McpError> {
+        Ok(handle_cpu_info())
+    }
+
+    #[tool(
+        description = "Read temperature sensors from /sys/class/thermal and hwmon, flagging anything above 80°C"
+    )]
+    async fn temperature(&self) -> Result<CallToolResult, McpError> {
+        Ok(handle_temperature())
+    }
+
+    #[tool(
+        description = "List user and system cron entries with their source, or add a new entry to the user crontab. Actions: list, add"
+    )]
+    async fn cron(
+        &self,
+        Parameters(req): Parameters<CronRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        Ok(handle_cron(req).await)
+    }
+
+    #[tool(
+        description = "Show error-level (or worse) systemd journal messages from the last hour, grouped by unit"
+    )]
+    async fn journal_errors(&self) -> Result<CallToolResult, McpError> {
+        Ok(handle_journal_errors().await)
+    }
+
+    #[tool(
+        description = "Check for and install system package updates using pacman, apt, dnf or brew (detected in that order). Actions: list_updates, install, update"
+    )]
+    async fn package(
+        &self,
+        Parameters(req): Parameters<PackageRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        Ok(handle_package(req).await)
+    }
+
+    #[tool(
+        description = "Manage asdf runtime versions and report Elixir projects whose .tool-versions differ from the global versions. Actions: list_all, current, install, set_local, check_projects"
+    )]
+    async fn asdf_versions(
+        &self,
+        Parameters(req): Parameters<AsdfRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        Ok(handle_asdf(req).await)
+    }
+
+    #[tool(description = "Show the active tool versions managed by mise")]
+    async fn mise_current(&self) -> Result<CallToolResult, McpError> {
+        Ok(handle_mise_current().await)
+    }
+
+    #[tool(description = "Manage tmux sessions. Actions: list, new, attach, kill, send_keys")]
+    async fn tmux(
+        &self,
+        Parameters(req): Parameters<TmuxRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        Ok(handle_tmux(req).await)
+    }
+
+    #[tool(
+        description = "Browse clipboard history via cliphist (or an in-memory history when cliphist is missing). Actions: list, search, select"
+    )]
+    async fn clipboard_history(
+        &self,
+        Parameters(req): Parameters<ClipboardHistoryRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        Ok(handle_clipboard_history(req).await)
+    }
+
+    #[tool(
+        description = "List window manager keybindings (sway, i3 or GNOME) as sorted key → action pairs"
+    )]
+    async fn keybinding_list(&self) -> Result<CallToolResult, McpError> {
+        Ok(handle_keybinding_list().await)
+    }
+
+    #[tool(
+        description = "Control PulseAudio/PipeWire output volume. Actions: get, set, mute, unmute, list_sinks"
+    )]
+    async fn volume(
+        &self,
+        Parameters(req): Parameters<VolumeRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        Ok(handle_volume(req).await)
+    }
+
+    #[tool(
+        description = "Read or set display brightness via the sysfs backlight, or xrandr for external monitors. Actions: get, set"
+    )]
+    async fn screen_brightness(
+        &self,
+        Parameters(req): Parameters<BrightnessRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        Ok(handle_brightness(req).await)
+    }
+
+    #[tool(
+        description = "List and arrange displays with xrandr. Actions: list, set_resolution, mirror, extend"
+    )]
+    async fn xrandr_displays(
+        &self,
+        Parameters(req): Parameters<DisplayRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        Ok(handle_displays(req).await)
+    }
+
+    #[tool(
+        description = "Capture a screenshot of the whole screen or the active window (grim on Wayland, scrot or ImageMagick import on X11). Actions: capture"
+    )]
+    async fn screenshot(
+        &self,
+        Parameters(req): Parameters<ScreenshotRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        Ok(handle_screenshot(req).await)
+    }
+
+    #[tool(
+        description = "Launch a desktop application listed in ~/.config/steve/allowed_apps.toml, by executable or .desktop file name"
+    )]
+    async fn application_launch(
+        &self,
+        Parameters(req): Parameters<LaunchRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        Ok(handle_launch(req).await)
+    }
+
+    #[tool(
+        description = "Generate random passwords from lowercase, uppercase, number and symbol characters. Passwords are never logged or cached"
+    )]
+    async fn generate_password(
+        &self,
+        Parameters(req): Parameters<PasswordGenRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        Ok(handle_generate_password(req))
+    }
+
+    #[tool(
+        description = "Decode a JSON Web Token and show its header, payload and expiry. Does NOT verify the signature; use only for debugging"
+    )]
+    async fn jwt_decode(
+        &self,
+        Parameters(req): Parameters<JwtDecodeRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        Ok(handle_jwt_decode(req))
+    }
+
+    #[tool(
+        description = "Check that a TOML, YAML or JSON document parses, reporting the error position when it does not"
+    )]
+    async fn validate(
+        &self,
+        Parameters(req): Parameters<ValidateRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        Ok(handle_validate(req))
+    }
+
+    #[tool(
+        description = "Test a regular expression against sample input, returning the first match with its groups or all matches"
+    )]
+    async fn regex_test(
+        &self,
+        Parameters(req): Parameters<RegexTestRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        Ok(handle_regex_test(req))
+    }
+
+    #[tool(description = "Parse, compare or bump semantic versions. Actions: parse, compare, bump")]
+    async fn semver(
+        &self,
+        Parameters(req): Parameters<SemverRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        Ok(handle_semver(req))
+    }
+
+    #[tool(
+        description = "Convert a timestamp (RFC 3339, Unix epoch or common formats; default now) between IANA timezones and format it with strftime"
+    )]
+    async fn time_convert(
+        &self,
+        Parameters(req): Parameters<TimeRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        Ok(handle_time_convert(req))
+    }
+
+    #[tool(
+        description = "Convert a color between hex, rgb() and hsl() notations; accepts CSS color names"
+    )]
+    async fn color_convert(
+        &self,
+        Parameters(req): Parameters<ColorRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        Ok(handle_color_convert(req))
+    }
+
+    #[tool(description = "Read man page synopsis")]
+    async fn man_page_summary(
+        &self,
+        Parameters(req): Parameters<ManPageRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        Ok(handle_man_page(req).await)
+    }
+
+    #[tool(
+        description = "Show the tldr page (common usage examples) for a command, cached for 7 days"
+    )]
+    async fn tldr_lookup(
+        &self,
+        Parameters(req): Parameters<TldrRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        Ok(handle_tldr(req).await)
+    }
+
+    #[tool(
+        description = "Keep Markdown notes in ~/.local/share/steve/notes. Actions: add, list, get, search, delete"
+    )]
+    async fn note(
+        &self,
+        Parameters(req): Parameters<NoteRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        Ok(handle_note(req))
+    }
+
+    #[tool(
+        description = "Track todos in ~/.local/share/steve/todos.json. Actions: add, list, done, delete, clear"
+    )]
+    async fn todo(
+        &self,
+        Parameters(req): Parameters<TodoRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        Ok(handle_todo(req))
+    }
+
+    #[tool(
+        description = "Save, search and open URL bookmarks stored in ~/.local/share/steve/bookmarks.jsonl. Actions: add, list, search, delete, open"
+    )]
+    async fn bookmark(
+        &self,
+        Parameters(req): Parameters<BookmarkRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        Ok(handle_bookmark(req))
+    }
+
+    #[tool(
+        description = "Named stopwatches and countdown timers. Actions: start, stop, status, timer (returns when the countdown ends)"
+    )]
+    async fn timer(
+        &self,
+        Parameters(req): Parameters<TimerRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        Ok(handle_timer(req, &self.stopwatches).await)
+    }
+
+    #[tool(description = "Count lines, words, characters and bytes in text or a file, like wc")]
+    async fn word_count(
+        &self,
+        Parameters(req): Parameters<WordCountRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        Ok(handle_word_count(req))
+    }
+
+    #[tool(description = "Compute a unified diff between two texts")]
+    async fn diff_text(
+        &self,
+        Parameters(req): Parameters<DiffRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        Ok(handle_diff_text(req))
+    }
+
+    #[tool(
+        description = "Render a template by replacing {{variable}} placeholders with the given values"
+    )]
+    async fn template_render(
+        &self,
+        Parameters(req): Parameters<TemplateRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        Ok(handle_template_render(req))
+    }
+
+    #[tool(
+        description = "List every Steve tool as a Markdown guide with parameters and example invocations"
+    )]
+    async fn list_tools(&self) -> Result<CallToolResult, McpError> {
+        Ok(handle_list_tools(self.tool_router.list_all()))
+    }
+
+    #[tool(
+        description = "Show the most recent tool calls from ~/.cache/steve/audit.log (timestamp, tool, request, duration, success)"
+    )]
+    async fn audit_log(
+        &self,
+        Parameters(req): Parameters<AuditLogRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        Ok(handle_audit_log(req))
+    }
+
+    #[tool(description = "Show the last failed tool call (tool, request and error) and clear it")]
+    async fn last_error(&self) -> Result<CallToolResult, McpError> {
+        Ok(handle_last_error(&self.last_error))
     }
 }
 
-#[tool_handler]
 impl ServerHandler for Steve {
     fn get_info(&self) -> ServerInfo {
         ServerInfo {
@@ -59,13 +531,119 @@ impl ServerHandler for Steve {
             instructions: Some("Steve - a local MCP server for system tasks".to_string()),
         }
     }
+
+    async fn call_tool(
+        &self,
+        request: CallToolRequestParam,
+        context: RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, McpError> {
+        let tool = request.name.to_string();
+        let arguments = request.arguments.clone();
+        let started = Instant::now();
+
+        let tcc = ToolCallContext::new(self, request, context);
+        let result = self.tool_router.call(tcc).await;
+
+        let error = call_error(&result);
+        write_audit_entry(
+            &self.audit_log,
+            &tool,
+            arguments.as_ref(),
+            started.elapsed(),
+            error.is_none(),
+        );
+        // Don't let reading the last error replace it with its own result
+        if let Some(error) = error
+            && tool != "last_error"
+        {
+            let request = serde_json::to_string(&arguments).unwrap_or_default();
+            *self.last_error.lock().unwrap_or_else(|e| e.into_inner()) =
+                Some((tool, request, error));
+        }
+        result
+    }
+
+    async fn list_tools(
+        &self,
+        _request: Option<PaginatedRequestParam>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListToolsResult, McpError> {
+        Ok(ListToolsResult::with_all_items(self.tool_router.list_all()))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Transport {
+    /// MCP over stdin/stdout
+    Stdio,
+    /// Streamable HTTP without sessions, each POST answered on its own
+    Http,
+    /// Streamable HTTP with sessions and a Server-Sent Events stream per client
+    Sse,
+}
+
+#[derive(Debug, Parser)]
+#[command(version, about = "Steve - a local MCP server for system tasks")]
+struct Args {
+    /// Transport to serve MCP over
+    #[arg(long, value_enum, default_value_t = Transport::Stdio)]
+    transport: Transport,
+    /// Address the http and sse transports listen on
+    #[arg(long, default_value = "127.0.0.1:3000")]
+    bind: SocketAddr,
+}
+
+fn main() -> anyhow::Result<()> {
+    let runtime = tokio::runtime::Runtime::new()?;
+    let result = runtime.block_on(run());
+    // Dropping the remaining tasks kills their child processes. Stdin is read
+    // on a blocking thread that cannot be interrupted, so don't wait for it.
+    runtime.shutdown_timeout(Duration::from_secs(1));
+    result
 }
 
-#[tokio::main]
-async fn main() -> anyhow::Result<()> {
+async fn run() -> anyhow::Result<()> {
+    let args = Args::parse();
+    cancel_on_sigterm()?;
     let service = Steve::new();
+
+    match args.transport {
+        Transport::Stdio => tokio::select! {
+            result = serve_stdio(service) => result?,
+            _ = shutdown_token().cancelled() => {}
+        },
+        Transport::Http => serve_http(service, args.bind, false).await?,
+        Transport::Sse => serve_http(service, args.bind, true).await?,
+    }
+    Ok(())
+}
+
+async fn serve_stdio(service: Steve) -> anyhow::Result<()> {
     let transport = (stdin(), stdout());
     let server = service.serve(transport).await?;
     server.waiting().await?;
     Ok(())
 }
+
+/// Serve MCP at `http://<bind>/mcp`. Every session shares one `Steve`, so
+/// stopwatches and the audit log are the same across clients.
+async fn serve_http(service: Steve, bind: SocketAddr, stateful: bool) -> anyhow::Result<()> {
+    let config = StreamableHttpServerConfig {
+        stateful_mode: stateful,
+        cancellation_token: shutdown_token().child_token(),
+        ..Default::default()
+    };
+    let mcp = StreamableHttpService::new(
+        move || Ok(service.clone()),
+        Arc::new(LocalSessionManager::default()),
+        config,
+    );
+
+    let router = axum::Router::new().nest_service("/mcp", mcp);
+    let listener = tokio::net::TcpListener::bind(bind).await?;
+    eprintln!("Steve listening on http://{}/mcp", listener.local_addr()?);
+    axum::serve(listener, router)
+        .with_graceful_shutdown(shutdown_token().clone().cancelled_owned())
+        .await?;
+    Ok(())
+}
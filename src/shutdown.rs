@@ -0,0 +1,22 @@
+use std::sync::LazyLock;
+use tokio::signal::unix::{SignalKind, signal};
+use tokio_util::sync::CancellationToken;
+
+static SHUTDOWN: LazyLock<CancellationToken> = LazyLock::new(CancellationToken::new);
+
+/// Cancelled once Steve receives SIGTERM. Batch actions check it between
+/// projects and running child processes are killed.
+pub fn shutdown_token() -> &'static CancellationToken {
+    &SHUTDOWN
+}
+
+/// Cancel the shutdown token when SIGTERM arrives.
+pub fn cancel_on_sigterm() -> std::io::Result<()> {
+    let mut sigterm = signal(SignalKind::terminate())?;
+    tokio::spawn(async move {
+        if sigterm.recv().await.is_some() {
+            SHUTDOWN.cancel();
+        }
+    });
+    Ok(())
+}
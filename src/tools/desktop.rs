@@ -0,0 +1,1016 @@
+use rmcp::model::{CallToolResult, Content};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::Mutex;
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+
+use super::elixir_projects::expand_path;
+use super::system::{dir_entries_with_prefix, find_in_path, read_trimmed};
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct TmuxRequest {
+    #[schemars(description = "Action to perform: list, new, attach, kill, send_keys")]
+    pub action: String,
+    #[schemars(description = "Session name (required for all actions except list)")]
+    pub session: Option<String>,
+    #[schemars(
+        description = "Window name or index (new: name of the first window, send_keys: target window)"
+    )]
+    pub window: Option<String>,
+    #[schemars(description = "Command to type into the pane for send_keys")]
+    pub command: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ClipboardHistoryRequest {
+    #[schemars(description = "Action to perform: list, search, select")]
+    pub action: String,
+    #[schemars(description = "Text to look for in clipboard entries (search)")]
+    pub query: Option<String>,
+    #[schemars(description = "Index of the entry to restore, as shown by list (select)")]
+    pub index: Option<usize>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct VolumeRequest {
+    #[schemars(description = "Action to perform: get, set, mute, unmute, list_sinks")]
+    pub action: String,
+    #[schemars(description = "Sink name or index (default: @DEFAULT_SINK@)")]
+    pub sink: Option<String>,
+    #[schemars(description = "Volume percentage for set (0-150)")]
+    pub volume: Option<u32>,
+    #[schemars(description = "Mute state for mute (default: toggle)")]
+    pub mute: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct BrightnessRequest {
+    #[schemars(description = "Action to perform: get, set")]
+    pub action: String,
+    #[schemars(description = "Brightness percentage for set (5-100)")]
+    pub value: Option<u32>,
+    #[schemars(
+        description = "xrandr output name (e.g. HDMI-1) for external monitors; defaults to the built-in backlight"
+    )]
+    pub display: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct DisplayRequest {
+    #[schemars(description = "Action to perform: list, set_resolution, mirror, extend")]
+    pub action: String,
+    #[schemars(description = "xrandr output name, e.g. HDMI-1 (required except for list)")]
+    pub display: Option<String>,
+    #[schemars(description = "Mode for set_resolution, e.g. 1920x1080")]
+    pub resolution: Option<String>,
+    #[schemars(
+        description = "Placement relative to the primary display for extend: left-of, right-of (default), above, below"
+    )]
+    pub position: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ScreenshotRequest {
+    #[schemars(description = "Action to perform: capture")]
+    pub action: String,
+    #[schemars(description = "Where to save the PNG (default: ~/Screenshots/<timestamp>.png)")]
+    pub output_path: Option<String>,
+    #[schemars(description = "Capture only the active window instead of the whole screen")]
+    pub window: Option<bool>,
+    #[schemars(description = "Seconds to wait before capturing (max 60)")]
+    pub delay_secs: Option<u32>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct LaunchRequest {
+    #[schemars(
+        description = "Executable or desktop file name (e.g. firefox, org.gnome.Nautilus); must be listed in ~/.config/steve/allowed_apps.toml"
+    )]
+    pub app: String,
+    #[schemars(description = "Extra arguments passed to the application")]
+    pub args: Option<Vec<String>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AllowedApps {
+    apps: Vec<String>,
+}
+
+const CLIPBOARD_HISTORY_LEN: usize = 20;
+
+/// Clipboard contents seen by this process, newest first, used when cliphist
+/// is not installed.
+static CLIPBOARD_RING: Mutex<VecDeque<String>> = Mutex::new(VecDeque::new());
+
+pub async fn handle_tmux(req: TmuxRequest) -> CallToolResult {
+    let result = match (req.action.as_str(), req.session.as_deref()) {
+        ("list", _) => {
+            run_tmux(&[
+                "list-sessions",
+                "-F",
+                "#{session_name}: #{session_windows} windows, created #{t:session_created}",
+            ])
+            .await
+        }
+        ("new", Some(session)) => {
+            let mut args = vec!["new-session", "-d", "-s", session];
+            if let Some(ref window) = req.window {
+                args.extend(["-n", window]);
+            }
+            run_tmux(&args)
+                .await
+                .map(|_| format!("✓ Created session '{}'", session))
+        }
+        // There is no terminal to attach from, so move the current client instead
+        ("attach", Some(session)) => run_tmux(&["switch-client", "-t", session])
+            .await
+            .map(|_| format!("✓ Switched client to session '{}'", session)),
+        ("kill", Some(session)) => run_tmux(&["kill-session", "-t", session])
+            .await
+            .map(|_| format!("✓ Killed session '{}'", session)),
+        ("send_keys", Some(session)) => match req.command {
+            Some(ref command) => {
+                let target = match req.window {
+                    Some(ref window) => format!("{}:{}", session, window),
+                    None => session.to_string(),
+                };
+                // -l types the text literally, even if it looks like a key
+                // name such as C-c or Escape
+                match run_tmux(&["send-keys", "-l", "-t", &target, "--", command]).await {
+                    Ok(_) => run_tmux(&["send-keys", "-t", &target, "Enter"])
+                        .await
+                        .map(|_| format!("✓ Sent to {}: {}", target, command)),
+                    Err(e) => Err(e),
+                }
+            }
+            None => Err("Error: 'command' is required for send_keys action".to_string()),
+        },
+        ("new" | "attach" | "kill" | "send_keys", None) => Err(format!(
+            "Error: 'session' is required for {} action",
+            req.action
+        )),
+        _ => Err(format!(
+            "Unknown action '{}'. Use: list, new, attach, kill, send_keys",
+            req.action
+        )),
+    };
+
+    match result {
+        Ok(output) => CallToolResult::success(vec![Content::text(output)]),
+        Err(e) => CallToolResult::success(vec![Content::text(e)]),
+    }
+}
+
+async fn run_tmux(args: &[&str]) -> Result<String, String> {
+    match Command::new("tmux").args(args).output().await {
+        Ok(output) => {
+            if output.status.success() {
+                Ok(String::from_utf8_lossy(&output.stdout).to_string())
+            } else {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                Err(format!("tmux error: {}", stderr.trim()))
+            }
+        }
+        Err(e) => Err(format!("Failed to run tmux: {}", e)),
+    }
+}
+
+pub async fn handle_clipboard_history(req: ClipboardHistoryRequest) -> CallToolResult {
+    let use_cliphist = find_in_path("cliphist").is_some();
+    let entries = if use_cliphist {
+        cliphist_entries().await
+    } else {
+        ring_entries().await
+    };
+    let entries = match entries {
+        Ok(entries) => entries,
+        Err(e) => return CallToolResult::success(vec![Content::text(e)]),
+    };
+
+    let result = match req.action.as_str() {
+        "list" => Ok(format_clipboard_entries(
+            entries.iter().enumerate().take(CLIPBOARD_HISTORY_LEN),
+        )),
+        "search" => match req.query {
+            Some(ref query) => {
+                let query = query.to_lowercase();
+                Ok(format_clipboard_entries(entries.iter().enumerate().filter(
+                    |(_, entry)| entry.text.to_lowercase().contains(&query),
+                )))
+            }
+            None => Err("Error: 'query' is required for search action".to_string()),
+        },
+        "select" => match req.index.map(|index| (index, entries.get(index))) {
+            Some((_, Some(entry))) => {
+                let contents = if use_cliphist {
+                    pipe_through("cliphist", &["decode"], entry.raw.as_bytes()).await
+                } else {
+                    Ok(entry.raw.clone().into_bytes())
+                };
+                match contents {
+                    Ok(contents) => copy_to_clipboard(&contents)
+                        .await
+                        .map(|_| format!("✓ Restored to clipboard: {}", preview(&entry.text))),
+                    Err(e) => Err(e),
+                }
+            }
+            Some((index, None)) => Err(format!("Error: no clipboard entry at index {}", index)),
+            None => Err("Error: 'index' is required for select action".to_string()),
+        },
+        _ => Err(format!(
+            "Unknown action '{}'. Use: list, search, select",
+            req.action
+        )),
+    };
+
+    match result {
+        Ok(output) => CallToolResult::success(vec![Content::text(output)]),
+        Err(e) => CallToolResult::success(vec![Content::text(e)]),
+    }
+}
+
+pub async fn handle_keybinding_list() -> CallToolResult {
+    let desktop = env::var("XDG_CURRENT_DESKTOP")
+        .unwrap_or_default()
+        .to_lowercase();
+    let config_dir = dirs::config_dir().unwrap_or_default();
+    let sway_config = config_dir.join("sway/config");
+    let i3_config = config_dir.join("i3/config");
+
+    let result = if desktop.contains("gnome") {
+        gnome_keybindings().await
+    } else if desktop.contains("sway") || (!desktop.contains("i3") && sway_config.exists()) {
+        bindsym_keybindings(&sway_config)
+    } else if desktop.contains("i3") || i3_config.exists() {
+        bindsym_keybindings(&i3_config)
+    } else {
+        Err("Could not detect a supported window manager (sway, i3, GNOME)".to_string())
+    };
+
+    let text = match result {
+        Ok(mut bindings) if !bindings.is_empty() => {
+            bindings.sort();
+            bindings.dedup();
+            bindings
+                .iter()
+                .map(|(key, action)| format!("{} → {}", key, action))
+                .collect::<Vec<_>>()
+                .join("\n")
+        }
+        Ok(_) => "No keybindings found".to_string(),
+        Err(e) => e,
+    };
+    CallToolResult::success(vec![Content::text(text)])
+}
+
+struct ClipboardEntry {
+    /// The line cliphist decodes from, or the full contents for ring entries
+    raw: String,
+    text: String,
+}
+
+async fn cliphist_entries() -> Result<Vec<ClipboardEntry>, String> {
+    let output = Command::new("cliphist")
+        .arg("list")
+        .output()
+        .await
+        .map_err(|e| format!("Failed to run cliphist: {}", e))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("cliphist error: {}", stderr.trim()));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(|line| ClipboardEntry {
+            raw: line.to_string(),
+            text: line
+                .split_once('\t')
+                .map_or(line, |(_, text)| text)
+                .to_string(),
+        })
+        .collect())
+}
+
+/// Record the current clipboard in the in-memory ring and return its entries.
+async fn ring_entries() -> Result<Vec<ClipboardEntry>, String> {
+    let current = if env::var_os("WAYLAND_DISPLAY").is_some() {
+        Command::new("wl-paste").arg("--no-newline").output().await
+    } else {
+        Command::new("xclip")
+            .args(["-selection", "clipboard", "-o"])
+            .output()
+            .await
+    };
+    let current = current
+        .ok()
+        .filter(|o| o.status.success())
+        .map(|o| String::from_utf8_lossy(&o.stdout).to_string())
+        .filter(|text| !text.is_empty());
+
+    let mut ring = CLIPBOARD_RING.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(current) = current
+        && ring.front() != Some(&current)
+    {
+        ring.push_front(current);
+        ring.truncate(CLIPBOARD_HISTORY_LEN);
+    }
+    if ring.is_empty() {
+        return Err(
+            "No clipboard history: cliphist is not installed and the clipboard could not be read"
+                .to_string(),
+        );
+    }
+
+    Ok(ring
+        .iter()
+        .map(|text| ClipboardEntry {
+            raw: text.clone(),
+            text: text.clone(),
+        })
+        .collect())
+}
+
+fn format_clipboard_entries<'a>(
+    entries: impl Iterator<Item = (usize, &'a ClipboardEntry)>,
+) -> String {
+    let lines: Vec<String> = entries
+        .map(|(index, entry)| format!("{}: {}", index, preview(&entry.text)))
+        .collect();
+    if lines.is_empty() {
+        "No clipboard entries found".to_string()
+    } else {
+        lines.join("\n")
+    }
+}
+
+fn preview(text: &str) -> String {
+    let line = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if line.chars().count() > 100 {
+        format!("{}...", line.chars().take(100).collect::<String>())
+    } else {
+        line
+    }
+}
+
+/// Run `program` with `input` on stdin and return its stdout.
+pub(crate) async fn pipe_through(
+    program: &str,
+    args: &[&str],
+    input: &[u8],
+) -> Result<Vec<u8>, String> {
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to run {}: {}", program, e))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(input)
+            .await
+            .map_err(|e| format!("Failed to write to {}: {}", program, e))?;
+    }
+    let output = child
+        .wait_with_output()
+        .await
+        .map_err(|e| format!("Failed to run {}: {}", program, e))?;
+
+    if output.status.success() {
+        Ok(output.stdout)
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        Err(format!("{} error: {}", program, stderr.trim()))
+    }
+}
+
+async fn copy_to_clipboard(contents: &[u8]) -> Result<(), String> {
+    let (program, args): (&str, &[&str]) = if env::var_os("WAYLAND_DISPLAY").is_some() {
+        ("wl-copy", &[])
+    } else {
+        ("xclip", &["-selection", "clipboard", "-i"])
+    };
+
+    // Both tools fork to keep serving the selection; a captured stdout would
+    // stay open until the clipboard changes
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| format!("Failed to run {}: {}", program, e))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(contents)
+            .await
+            .map_err(|e| format!("Failed to write to {}: {}", program, e))?;
+    }
+    let status = child
+        .wait()
+        .await
+        .map_err(|e| format!("Failed to run {}: {}", program, e))?;
+
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("{} exited with {}", program, status))
+    }
+}
+
+/// Read `bindsym` lines from a sway/i3 config, expanding `set $var value`
+/// variables.
+fn bindsym_keybindings(config: &Path) -> Result<Vec<(String, String)>, String> {
+    let contents = fs::read_to_string(config)
+        .map_err(|e| format!("Failed to read {}: {}", config.display(), e))?;
+
+    let mut variables: Vec<(String, String)> = Vec::new();
+    let mut bindings = Vec::new();
+    for line in contents.lines().map(str::trim) {
+        let mut words = line.split_whitespace();
+        match words.next() {
+            Some("set") => {
+                if let (Some(name), Some(value)) = (words.next(), words.next()) {
+                    let value = expand_variables(value, &variables);
+                    variables.push((name.to_string(), value));
+                    // Longest names first so $mod does not clobber $mod_alt
+                    variables.sort_by_key(|(name, _)| std::cmp::Reverse(name.len()));
+                }
+            }
+            Some("bindsym") => {
+                let mut words = words.skip_while(|w| w.starts_with("--"));
+                if let Some(key) = words.next() {
+                    let action = words.collect::<Vec<_>>().join(" ");
+                    bindings.push((
+                        expand_variables(key, &variables),
+                        expand_variables(&action, &variables),
+                    ));
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(bindings)
+}
+
+fn expand_variables(text: &str, variables: &[(String, String)]) -> String {
+    variables
+        .iter()
+        .fold(text.to_string(), |text, (name, value)| {
+            text.replace(name, value)
+        })
+}
+
+async fn gnome_keybindings() -> Result<Vec<(String, String)>, String> {
+    let output = Command::new("gsettings")
+        .args(["list-recursively", "org.gnome.desktop.wm.keybindings"])
+        .output()
+        .await
+        .map_err(|e| format!("Failed to run gsettings: {}", e))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("gsettings error: {}", stderr.trim()));
+    }
+
+    // "org.gnome.desktop.wm.keybindings close ['<Super>q', '<Alt>F4']"
+    let mut bindings = Vec::new();
+    for line in String::from_utf8_lossy(&output.stdout).lines() {
+        let mut parts = line.splitn(3, ' ');
+        let (Some(_), Some(action), Some(keys)) = (parts.next(), parts.next(), parts.next()) else {
+            continue;
+        };
+        for key in keys
+            .trim_start_matches("@as ")
+            .trim_matches(|c| c == '[' || c == ']')
+            .split(',')
+            .map(|k| k.trim().trim_matches('\''))
+            .filter(|k| !k.is_empty())
+        {
+            bindings.push((key.to_string(), action.to_string()));
+        }
+    }
+    Ok(bindings)
+}
+
+pub async fn handle_volume(req: VolumeRequest) -> CallToolResult {
+    let sink = req.sink.as_deref().unwrap_or("@DEFAULT_SINK@");
+
+    let result = match req.action.as_str() {
+        "get" => sink_volume(sink).await,
+        "set" => match req.volume {
+            Some(volume) => {
+                let volume = format!("{}%", volume.min(150));
+                match run_pactl(&["set-sink-volume", sink, &volume]).await {
+                    Ok(_) => sink_volume(sink).await,
+                    Err(e) => Err(e),
+                }
+            }
+            None => Err("Error: 'volume' is required for set action".to_string()),
+        },
+        "mute" | "unmute" => {
+            let state = match (req.action.as_str(), req.mute) {
+                ("unmute", _) | (_, Some(false)) => "0",
+                (_, Some(true)) => "1",
+                _ => "toggle",
+            };
+            match run_pactl(&["set-sink-mute", sink, state]).await {
+                Ok(_) => sink_volume(sink).await,
+                Err(e) => Err(e),
+            }
+        }
+        "list_sinks" => run_pactl(&["list", "sinks", "short"]).await,
+        _ => Err(format!(
+            "Unknown action '{}'. Use: get, set, mute, unmute, list_sinks",
+            req.action
+        )),
+    };
+
+    match result {
+        Ok(output) => CallToolResult::success(vec![Content::text(output)]),
+        Err(e) => CallToolResult::success(vec![Content::text(e)]),
+    }
+}
+
+/// Report volume and mute state, e.g. "Volume: 50% (muted)".
+async fn sink_volume(sink: &str) -> Result<String, String> {
+    // "Volume: front-left: 32768 /  50% / -18.06 dB,   front-right: ..."
+    let volume = run_pactl(&["get-sink-volume", sink]).await?;
+    let percent = volume
+        .split_whitespace()
+        .find(|word| word.ends_with('%'))
+        .ok_or_else(|| format!("Could not parse volume from: {}", volume.trim()))?;
+
+    let muted = run_pactl(&["get-sink-mute", sink])
+        .await
+        .is_ok_and(|mute| mute.trim() == "Mute: yes");
+
+    Ok(format!(
+        "Volume: {}{}",
+        percent,
+        if muted { " (muted)" } else { "" }
+    ))
+}
+
+async fn run_pactl(args: &[&str]) -> Result<String, String> {
+    match Command::new("pactl").args(args).output().await {
+        Ok(output) => {
+            if output.status.success() {
+                Ok(String::from_utf8_lossy(&output.stdout).to_string())
+            } else {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                Err(format!("pactl error: {}", stderr.trim()))
+            }
+        }
+        Err(e) => Err(format!("Failed to run pactl: {}", e)),
+    }
+}
+
+pub async fn handle_brightness(req: BrightnessRequest) -> CallToolResult {
+    let backlight = dir_entries_with_prefix(Path::new("/sys/class/backlight"), "")
+        .into_iter()
+        .next();
+
+    let result = match (req.action.as_str(), req.display.as_deref(), backlight) {
+        ("get", None, Some(backlight)) => read_backlight(&backlight)
+            .map(|(current, max)| format!("Brightness: {}%", current * 100 / max.max(1))),
+        ("get", Some(display), _) => xrandr_brightness(display).await,
+        ("set", display, backlight) => match req.value {
+            Some(value) => {
+                let value = value.clamp(5, 100);
+                match (display, backlight) {
+                    (None, Some(backlight)) => set_backlight(&backlight, value),
+                    (Some(display), _) => {
+                        let level = format!("{:.2}", value as f64 / 100.0);
+                        run_xrandr(&["--output", display, "--brightness", &level])
+                            .await
+                            .map(|_| format!("✓ {} brightness set to {}%", display, value))
+                    }
+                    (None, None) => {
+                        Err("No backlight device found; pass 'display' to use xrandr".to_string())
+                    }
+                }
+            }
+            None => Err("Error: 'value' is required for set action".to_string()),
+        },
+        ("get", None, None) => {
+            Err("No backlight device found; pass 'display' to use xrandr".to_string())
+        }
+        _ => Err(format!("Unknown action '{}'. Use: get, set", req.action)),
+    };
+
+    match result {
+        Ok(output) => CallToolResult::success(vec![Content::text(output)]),
+        Err(e) => CallToolResult::success(vec![Content::text(e)]),
+    }
+}
+
+fn read_backlight(backlight: &Path) -> Result<(u64, u64), String> {
+    let read = |name: &str| {
+        read_trimmed(&backlight.join(name))
+            .and_then(|v| v.parse::<u64>().ok())
+            .ok_or_else(|| format!("Failed to read {}", backlight.join(name).display()))
+    };
+    Ok((read("brightness")?, read("max_brightness")?))
+}
+
+fn set_backlight(backlight: &Path, percent: u32) -> Result<String, String> {
+    let (_, max) = read_backlight(backlight)?;
+    let raw = max * percent as u64 / 100;
+    let path = backlight.join("brightness");
+    fs::write(&path, raw.to_string()).map_err(|e| {
+        format!(
+            "Failed to write {}: {} (the user needs write access, e.g. via a udev rule or the video group)",
+            path.display(),
+            e
+        )
+    })?;
+    Ok(format!("✓ Brightness set to {}%", percent))
+}
+
+/// Read the software brightness xrandr applies to `display`.
+async fn xrandr_brightness(display: &str) -> Result<String, String> {
+    let output = run_xrandr(&["--verbose"]).await?;
+    output
+        .lines()
+        .skip_while(|line| !line.starts_with(&format!("{} ", display)))
+        .skip(1)
+        .take_while(|line| line.starts_with(char::is_whitespace))
+        .find_map(|line| line.trim().strip_prefix("Brightness:"))
+        .and_then(|level| level.trim().parse::<f64>().ok())
+        .map(|level| format!("{} brightness: {:.0}%", display, level * 100.0))
+        .ok_or_else(|| format!("Display '{}' not found in xrandr output", display))
+}
+
+async fn run_xrandr(args: &[&str]) -> Result<String, String> {
+    match Command::new("xrandr").args(args).output().await {
+        Ok(output) => {
+            if output.status.success() {
+                Ok(String::from_utf8_lossy(&output.stdout).to_string())
+            } else {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                Err(format!("xrandr error: {}", stderr.trim()))
+            }
+        }
+        Err(e) => Err(format!("Failed to run xrandr: {}", e)),
+    }
+}
+
+pub async fn handle_displays(req: DisplayRequest) -> CallToolResult {
+    let result = match (req.action.as_str(), req.display.as_deref()) {
+        ("list", _) => run_xrandr(&["--query"])
+            .await
+            .map(|output| format_displays(&parse_xrandr_query(&output))),
+        ("set_resolution", Some(display)) => match req.resolution {
+            Some(ref resolution) => run_xrandr(&["--output", display, "--mode", resolution])
+                .await
+                .map(|_| format!("✓ {} set to {}", display, resolution)),
+            None => Err("Error: 'resolution' is required for set_resolution action".to_string()),
+        },
+        ("mirror" | "extend", Some(display)) => match primary_display().await {
+            Ok(primary) if primary == display => Err(format!(
+                "Error: '{}' is the primary display; pass the display to arrange around it",
+                display
+            )),
+            Ok(primary) if req.action == "mirror" => {
+                run_xrandr(&["--output", display, "--auto", "--same-as", &primary])
+                    .await
+                    .map(|_| format!("✓ {} mirrors {}", display, primary))
+            }
+            Ok(primary) => {
+                let position = req.position.as_deref().unwrap_or("right-of");
+                if !["left-of", "right-of", "above", "below"].contains(&position) {
+                    Err(format!(
+                        "Error: invalid position '{}'. Use: left-of, right-of, above, below",
+                        position
+                    ))
+                } else {
+                    let flag = format!("--{}", position);
+                    run_xrandr(&["--output", display, "--auto", &flag, &primary])
+                        .await
+                        .map(|_| format!("✓ {} placed {} {}", display, position, primary))
+                }
+            }
+            Err(e) => Err(e),
+        },
+        ("set_resolution" | "mirror" | "extend", None) => Err(format!(
+            "Error: 'display' is required for {} action",
+            req.action
+        )),
+        _ => Err(format!(
+            "Unknown action '{}'. Use: list, set_resolution, mirror, extend",
+            req.action
+        )),
+    };
+
+    match result {
+        Ok(output) => CallToolResult::success(vec![Content::text(output)]),
+        Err(e) => CallToolResult::success(vec![Content::text(e)]),
+    }
+}
+
+struct XrandrOutput {
+    name: String,
+    primary: bool,
+    /// Geometry such as "1920x1080+0+0", absent when the output is off
+    geometry: Option<String>,
+    /// Each mode with its refresh rates; `*` marks the current rate, `+` the preferred
+    modes: Vec<(String, Vec<String>)>,
+}
+
+/// Parse the connected outputs from `xrandr --query`.
+fn parse_xrandr_query(output: &str) -> Vec<XrandrOutput> {
+    let mut outputs: Vec<XrandrOutput> = Vec::new();
+    let mut in_connected = false;
+
+    for line in output.lines() {
+        if line.starts_with(char::is_whitespace) {
+            // "   1920x1080     60.02*+  59.93"
+            if let Some(current) = outputs.last_mut().filter(|_| in_connected) {
+                let mut words = line.split_whitespace();
+                if let Some(mode) = words.next() {
+                    current
+                        .modes
+                        .push((mode.to_string(), words.map(str::to_string).collect()));
+                }
+            }
+            continue;
+        }
+
+        // "eDP-1 connected primary 1920x1080+0+0 (normal left ...) 344mm x 194mm"
+        let words: Vec<&str> = line.split_whitespace().collect();
+        in_connected = words.get(1) == Some(&"connected");
+        if in_connected {
+            outputs.push(XrandrOutput {
+                name: words[0].to_string(),
+                primary: words.get(2) == Some(&"primary"),
+                geometry: words[2..]
+                    .iter()
+                    .take_while(|w| !w.starts_with('('))
+                    .find(|w| w.contains('x') && w.contains('+'))
+                    .map(|w| w.to_string()),
+                modes: Vec::new(),
+            });
+        }
+    }
+    outputs
+}
+
+fn format_displays(outputs: &[XrandrOutput]) -> String {
+    if outputs.is_empty() {
+        return "No connected displays found".to_string();
+    }
+
+    let mut lines = Vec::new();
+    for output in outputs {
+        lines.push(format!(
+            "{}{}: {}",
+            output.name,
+            if output.primary { " (primary)" } else { "" },
+            output.geometry.as_deref().unwrap_or("off")
+        ));
+        for (mode, rates) in &output.modes {
+            lines.push(format!("  {} @ {}", mode, rates.join(", ")));
+        }
+    }
+    lines.join("\n")
+}
+
+async fn primary_display() -> Result<String, String> {
+    let outputs = parse_xrandr_query(&run_xrandr(&["--query"]).await?);
+    outputs
+        .iter()
+        .find(|o| o.primary)
+        .or_else(|| outputs.first())
+        .map(|o| o.name.clone())
+        .ok_or_else(|| "No connected displays found".to_string())
+}
+
+pub async fn handle_screenshot(req: ScreenshotRequest) -> CallToolResult {
+    let result = match req.action.as_str() {
+        "capture" => capture_screenshot(&req).await,
+        _ => Err(format!("Unknown action '{}'. Use: capture", req.action)),
+    };
+
+    match result {
+        Ok(output) => CallToolResult::success(vec![Content::text(output)]),
+        Err(e) => CallToolResult::success(vec![Content::text(e)]),
+    }
+}
+
+async fn capture_screenshot(req: &ScreenshotRequest) -> Result<String, String> {
+    const MAX_DELAY_SECS: u32 = 60;
+    if let Some(delay) = req.delay_secs.filter(|&d| d > MAX_DELAY_SECS) {
+        return Err(format!(
+            "Error: delay_secs {} is too long; the maximum is {}",
+            delay, MAX_DELAY_SECS
+        ));
+    }
+
+    let path = match req.output_path {
+        Some(ref path) => expand_path(path),
+        None => {
+            let timestamp = chrono::Local::now().format("%Y-%m-%d_%H-%M-%S");
+            expand_path(&format!("~/Screenshots/{}.png", timestamp))
+        }
+    };
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    let target = path.to_string_lossy().to_string();
+
+    if let Some(delay) = req.delay_secs {
+        tokio::time::sleep(Duration::from_secs(delay as u64)).await;
+    }
+
+    let wayland = env::var_os("WAYLAND_DISPLAY").is_some();
+    let region = if req.window.unwrap_or(false) {
+        Some(if wayland {
+            sway_focused_region().await?
+        } else {
+            xdotool_active_region().await?
+        })
+    } else {
+        None
+    };
+
+    let program = if wayland {
+        "grim"
+    } else if find_in_path("scrot").is_some() {
+        "scrot"
+    } else {
+        "import"
+    };
+    let args: Vec<String> = match (program, region) {
+        ("grim", Some((x, y, w, h))) => vec!["-g".into(), format!("{},{} {}x{}", x, y, w, h)],
+        ("scrot", Some((x, y, w, h))) => vec!["-a".into(), format!("{},{},{},{}", x, y, w, h)],
+        ("import", Some((x, y, w, h))) => vec![
+            "-window".into(),
+            "root".into(),
+            "-crop".into(),
+            format!("{}x{}+{}+{}", w, h, x, y),
+        ],
+        ("import", None) => vec!["-window".into(), "root".into()],
+        _ => Vec::new(),
+    };
+
+    let output = Command::new(program)
+        .args(&args)
+        .arg(&target)
+        .output()
+        .await
+        .map_err(|e| format!("Failed to run {}: {}", program, e))?;
+    if output.status.success() {
+        Ok(format!("✓ Saved screenshot to {}", target))
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        Err(format!("{} error: {}", program, stderr.trim()))
+    }
+}
+
+/// Geometry of the active X11 window as (x, y, width, height).
+async fn xdotool_active_region() -> Result<(i64, i64, i64, i64), String> {
+    let output = Command::new("xdotool")
+        .args(["getactivewindow", "getwindowgeometry", "--shell"])
+        .output()
+        .await
+        .map_err(|e| format!("Failed to run xdotool: {}", e))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("xdotool error: {}", stderr.trim()));
+    }
+
+    // "X=10\nY=20\nWIDTH=800\nHEIGHT=600\n..."
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let value = |key: &str| {
+        stdout
+            .lines()
+            .find_map(|line| line.strip_prefix(key)?.strip_prefix('='))
+            .and_then(|v| v.parse::<i64>().ok())
+            .ok_or_else(|| format!("xdotool did not report {}", key))
+    };
+    Ok((value("X")?, value("Y")?, value("WIDTH")?, value("HEIGHT")?))
+}
+
+/// Geometry of the focused sway window as (x, y, width, height).
+async fn sway_focused_region() -> Result<(i64, i64, i64, i64), String> {
+    let output = Command::new("swaymsg")
+        .args(["-t", "get_tree"])
+        .output()
+        .await
+        .map_err(|e| format!("Active window capture on Wayland needs sway: {}", e))?;
+    let tree: serde_json::Value = serde_json::from_slice(&output.stdout)
+        .map_err(|e| format!("Failed to parse swaymsg output: {}", e))?;
+
+    fn focused(node: &serde_json::Value) -> Option<&serde_json::Value> {
+        if node["focused"].as_bool() == Some(true) {
+            return Some(node);
+        }
+        ["nodes", "floating_nodes"]
+            .iter()
+            .filter_map(|key| node[key].as_array())
+            .flatten()
+            .find_map(focused)
+    }
+
+    let rect = &focused(&tree).ok_or("No focused window found")?["rect"];
+    let value = |key: &str| rect[key].as_i64().unwrap_or(0);
+    Ok((value("x"), value("y"), value("width"), value("height")))
+}
+
+pub async fn handle_launch(req: LaunchRequest) -> CallToolResult {
+    let text = match launch_app(&req) {
+        Ok(output) => output,
+        Err(e) => e,
+    };
+    CallToolResult::success(vec![Content::text(text)])
+}
+
+fn launch_app(req: &LaunchRequest) -> Result<String, String> {
+    let allowed_path = dirs::home_dir()
+        .map(|h| h.join(".config/steve/allowed_apps.toml"))
+        .unwrap_or_else(|| PathBuf::from("allowed_apps.toml"));
+    let allowed: AllowedApps = fs::read_to_string(&allowed_path)
+        .map_err(|_| {
+            format!(
+                "No allowlist found. Create {} with: apps = [\"{}\"]",
+                allowed_path.display(),
+                req.app
+            )
+        })
+        .and_then(|contents| {
+            toml::from_str(&contents)
+                .map_err(|e| format!("Failed to parse {}: {}", allowed_path.display(), e))
+        })?;
+    if !allowed.apps.iter().any(|app| app == &req.app) {
+        return Err(format!(
+            "Error: '{}' is not in {}",
+            req.app,
+            allowed_path.display()
+        ));
+    }
+
+    let mut command = desktop_file_command(&req.app).unwrap_or_else(|| vec![req.app.clone()]);
+    command.extend(req.args.iter().flatten().cloned());
+
+    // Own process group and no inherited stdio, so the app outlives the request
+    let child = Command::new(&command[0])
+        .args(&command[1..])
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .process_group(0)
+        .spawn()
+        .map_err(|e| format!("Failed to launch {}: {}", command[0], e))?;
+
+    Ok(format!(
+        "✓ Launched {} (pid {})",
+        command.join(" "),
+        child.id().unwrap_or_default()
+    ))
+}
+
+/// Open a URL or file with the desktop's default handler.
+pub(crate) fn xdg_open(target: &str) -> Result<(), String> {
+    Command::new("xdg-open")
+        .arg(target)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .process_group(0)
+        .spawn()
+        .map(|_| ())
+        .map_err(|e| format!("Failed to run xdg-open: {}", e))
+}
+
+/// Find `<name>.desktop` in the user and system application directories and
+/// return its `Exec` command with field codes such as `%U` removed.
+fn desktop_file_command(name: &str) -> Option<Vec<String>> {
+    let file_name = format!("{}.desktop", name.trim_end_matches(".desktop"));
+    let contents = [
+        dirs::data_dir().map(|d| d.join("applications")),
+        Some(PathBuf::from("/usr/share/applications")),
+    ]
+    .into_iter()
+    .flatten()
+    .find_map(|dir| fs::read_to_string(dir.join(&file_name)).ok())?;
+
+    let exec = contents
+        .lines()
+        .skip_while(|line| line.trim() != "[Desktop Entry]")
+        .find_map(|line| line.strip_prefix("Exec="))?;
+    let command: Vec<String> = exec
+        .split_whitespace()
+        .filter(|word| !(word.len() == 2 && word.starts_with('%')))
+        .map(|word| word.trim_matches('"').to_string())
+        .collect();
+    (!command.is_empty()).then_some(command)
+}
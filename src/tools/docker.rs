@@ -0,0 +1,177 @@
+use rmcp::model::{CallToolResult, Content};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use tokio::process::Command;
+
+use super::elixir_projects::{expand_path, find_elixir_projects};
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct DockerRequest {
+    #[schemars(
+        description = "Action to perform: ps, ps_all, logs, compose_status, compose_up, compose_down, compose_restart"
+    )]
+    pub action: String,
+    #[schemars(description = "Container name or ID")]
+    pub container: Option<String>,
+    #[schemars(description = "Image name")]
+    pub image: Option<String>,
+    #[schemars(description = "Number of log lines to return for logs (default 50)")]
+    pub log_lines: Option<usize>,
+    #[schemars(
+        description = "Directory containing a compose file (compose_* actions; defaults to matching Elixir projects)"
+    )]
+    pub path: Option<String>,
+    #[schemars(description = "Filter Elixir projects by name (compose_* actions without 'path')")]
+    pub project: Option<String>,
+}
+
+const PS_FORMAT: &str = "table {{.ID}}\t{{.Names}}\t{{.Image}}\t{{.Status}}\t{{.Ports}}";
+
+pub async fn handle_docker(req: DockerRequest) -> CallToolResult {
+    let result = match req.action.as_str() {
+        "ps" => run_docker(&["ps", "--format", PS_FORMAT]).await,
+        "ps_all" => run_docker(&["ps", "--all", "--format", PS_FORMAT]).await,
+        "logs" => match req.container {
+            Some(ref container) => {
+                let tail = req.log_lines.unwrap_or(50).to_string();
+                run_docker_combined(&["logs", "--since", "1h", "--tail", &tail, container]).await
+            }
+            None => Err("Error: 'container' is required for logs action".to_string()),
+        },
+        "compose_status" => {
+            run_compose(
+                &req,
+                &[
+                    "ps",
+                    "--format",
+                    "table {{.Service}}\t{{.Status}}\t{{.Ports}}",
+                ],
+            )
+            .await
+        }
+        "compose_up" => run_compose(&req, &["up", "-d"]).await,
+        "compose_down" => run_compose(&req, &["down"]).await,
+        "compose_restart" => run_compose(&req, &["restart"]).await,
+        _ => Err(format!(
+            "Unknown action '{}'. Use: ps, ps_all, logs, compose_status, compose_up, compose_down, compose_restart",
+            req.action
+        )),
+    };
+
+    match result {
+        Ok(output) => CallToolResult::success(vec![Content::text(output)]),
+        Err(e) => CallToolResult::success(vec![Content::text(e)]),
+    }
+}
+
+/// Run `docker compose <args>` in `path`, or in every matching Elixir project
+/// that has a compose file.
+async fn run_compose(req: &DockerRequest, args: &[&str]) -> Result<String, String> {
+    let dirs: Vec<PathBuf> = match req.path {
+        Some(ref path) => vec![expand_path(path)],
+        None => find_elixir_projects(req.project.as_deref())
+            .into_iter()
+            .filter(|p| has_compose_file(p))
+            .collect(),
+    };
+
+    if dirs.is_empty() {
+        return Err("No projects with a docker-compose.yml or compose.yml found".to_string());
+    }
+
+    let mut sections: Vec<String> = Vec::new();
+    for dir in &dirs {
+        let output = Command::new("docker")
+            .arg("compose")
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .await;
+
+        let text = match output {
+            Ok(o) if o.status.success() => {
+                // up/down/restart report progress on stderr
+                let stdout = String::from_utf8_lossy(&o.stdout);
+                let stderr = String::from_utf8_lossy(&o.stderr);
+                format!(
+                    "✓ {}\n{}{}",
+                    dir.display(),
+                    stdout.trim_end(),
+                    stderr.trim_end()
+                )
+            }
+            Ok(o) => {
+                let stderr = String::from_utf8_lossy(&o.stderr);
+                format!("✗ {} {}", dir.display(), stderr.trim())
+            }
+            Err(e) => format!("✗ {} Failed to run docker: {}", dir.display(), e),
+        };
+        sections.push(text);
+    }
+
+    Ok(sections.join("\n\n"))
+}
+
+fn has_compose_file(dir: &Path) -> bool {
+    [
+        "docker-compose.yml",
+        "docker-compose.yaml",
+        "compose.yml",
+        "compose.yaml",
+    ]
+    .iter()
+    .any(|name| dir.join(name).exists())
+}
+
+async fn run_docker(args: &[&str]) -> Result<String, String> {
+    match Command::new("docker").args(args).output().await {
+        Ok(output) => {
+            if output.status.success() {
+                Ok(String::from_utf8_lossy(&output.stdout).to_string())
+            } else {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                Err(format!("docker error: {}", stderr.trim()))
+            }
+        }
+        Err(e) => Err(format!("Failed to run docker: {}", e)),
+    }
+}
+
+/// Run docker with stdout and stderr sharing one pipe, so the output keeps
+/// the order in which the two streams were written.
+async fn run_docker_combined(args: &[&str]) -> Result<String, String> {
+    let args: Vec<String> = args.iter().map(|a| a.to_string()).collect();
+
+    tokio::task::spawn_blocking(move || {
+        let (mut reader, writer) =
+            std::io::pipe().map_err(|e| format!("Failed to create pipe: {}", e))?;
+        let stderr_writer = writer
+            .try_clone()
+            .map_err(|e| format!("Failed to create pipe: {}", e))?;
+
+        // The command must be dropped before reading so its pipe ends close
+        let mut child = {
+            let mut cmd = std::process::Command::new("docker");
+            cmd.args(&args).stdout(writer).stderr(stderr_writer);
+            cmd.spawn()
+                .map_err(|e| format!("Failed to run docker: {}", e))?
+        };
+
+        let mut bytes = Vec::new();
+        let _ = reader.read_to_end(&mut bytes);
+        let output = String::from_utf8_lossy(&bytes).to_string();
+        let status = child
+            .wait()
+            .map_err(|e| format!("Failed to run docker: {}", e))?;
+
+        if status.success() {
+            Ok(output)
+        } else {
+            Err(format!("docker error: {}", output.trim()))
+        }
+    })
+    .await
+    .map_err(|e| format!("Failed to run docker: {}", e))?
+}
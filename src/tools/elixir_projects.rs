@@ -1,61 +1,787 @@
+use notify::event::ModifyKind;
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use regex::Regex;
 use rmcp::model::{CallToolResult, Content};
 use schemars::JsonSchema;
+use semver::Version;
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
 use std::fs;
+use std::hash::{Hash, Hasher};
 use std::io::{BufRead, BufReader, Write};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::process::Output;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::{Arc, Mutex, Once};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use tokio::process::Command;
+use tokio::sync::watch;
 use walkdir::WalkDir;
 
+use super::system::read_tool_versions;
+use super::util::bump_version;
+use crate::config::load_config;
+use crate::shutdown::shutdown_token;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ElixirProjectsAction {
+    List,
+    UpdateDeps,
+    Outdated,
+    GitPull,
+    GitPush,
+    GitStatus,
+    Refresh,
+    Delete,
+    Ignore,
+    Unignore,
+    Watch,
+    PreCommitCheck,
+    DepsTree,
+    Xref,
+    EctoReset,
+    GitPushForceWithLease,
+    GitCommit,
+    GitAdd,
+    Test,
+    ChangelogSummary,
+    VersionBump,
+    Audit,
+    Coverage,
+    Import,
+    Export,
+    GitFetch,
+    Rebase,
+    LoadTest,
+    HealthCheck,
+    DockerBuild,
+    MiseCheck,
+    NixInfo,
+    NixBuild,
+    DepsGet,
+    CompileWarnings,
+    Stale,
+    GitRemote,
+    Summary,
+    DepGraph,
+    LockDiff,
+    RunInAll,
+    VersionMatrix,
+    GitSquashLocal,
+    CoverageBadge,
+    PortConflicts,
+    ArchiveDeps,
+    CheckEnv,
+}
+
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct ElixirProjectsRequest {
-    #[schemars(
-        description = "Action to perform: list, update_deps, outdated, git_pull, git_push, git_status, refresh, delete, ignore, unignore"
-    )]
-    pub action: String,
+    #[schemars(description = "Action to perform")]
+    pub action: ElixirProjectsAction,
     #[schemars(description = "Filter to a specific project by name (e.g., 'moneyclub')")]
     pub project: Option<String>,
     #[schemars(description = "Starting directory path (defaults to ~/src/flt)")]
     pub path: Option<String>,
+    #[schemars(description = "Package to root the dependency tree at (deps_tree only)")]
+    pub package: Option<String>,
+    #[schemars(description = "Xref mode: stats (default), callers, or calls")]
+    pub xref_mode: Option<String>,
+    #[schemars(
+        description = "Module to look up callers of (xref callers) or source file to list calls from (xref calls)"
+    )]
+    pub module: Option<String>,
+    #[schemars(description = "MIX_ENV to run with (ecto_reset defaults to 'test')")]
+    pub env: Option<String>,
+    #[schemars(
+        description = "Use 'git pull --rebase --autostash' instead of merging (git_pull only)"
+    )]
+    pub rebase: Option<bool>,
+    #[schemars(
+        description = "Include each command's output (up to 500 chars per project) for update_deps and git_pull"
+    )]
+    pub verbose: Option<bool>,
+    #[schemars(description = "Commit message (required for git_commit)")]
+    pub commit_message: Option<String>,
+    #[schemars(description = "Paths to stage for git_add (defaults to ['.'])")]
+    pub files: Option<Vec<String>>,
+    #[schemars(description = "Test file (optionally with :line) to run for the test action")]
+    pub test_file: Option<String>,
+    #[schemars(
+        description = "Tag filter passed to 'mix test --only' (e.g. 'focus' or 'tag:value')"
+    )]
+    pub test_pattern: Option<String>,
+    #[schemars(description = "Version component to bump: major, minor, or patch (default)")]
+    pub bump_type: Option<String>,
+    #[schemars(description = "Coverage percentage below which projects are flagged (default 70)")]
+    pub min_coverage: Option<f64>,
+    #[schemars(
+        description = "Set to true to actually delete; otherwise delete and archive_deps only show a preview"
+    )]
+    pub confirm: Option<bool>,
+    #[schemars(
+        description = "Directory names to skip while scanning, replacing the defaults (deps, _build, .elixir_ls, node_modules, .git, _checkouts)"
+    )]
+    pub skip_dirs: Option<Vec<String>>,
+    #[schemars(description = "Maximum directory depth to scan below the root (default 8)")]
+    pub scan_depth: Option<usize>,
+    #[schemars(
+        description = "Glob patterns of paths to skip while scanning, e.g. '**/archived/**' (adds to skip_dirs)"
+    )]
+    pub ignore_glob: Option<Vec<String>>,
+    #[schemars(description = "File with one project path per line (import only)")]
+    pub file: Option<String>,
+    #[schemars(
+        description = "File to write the project list to (export only, defaults to ~/.cache/steve/projects_export.txt)"
+    )]
+    pub output_file: Option<String>,
+    #[schemars(
+        description = "URL to load test (load_test only, defaults to http://localhost:4000/)"
+    )]
+    pub url: Option<String>,
+    #[schemars(description = "Total number of requests for load_test (default 200)")]
+    pub requests: Option<u32>,
+    #[schemars(description = "Number of concurrent workers for load_test (default 10)")]
+    pub concurrency: Option<u32>,
+    #[schemars(description = "Image tag for docker_build (defaults to '<project>:latest')")]
+    pub docker_tag: Option<String>,
+    #[schemars(description = "Build without the Docker layer cache (docker_build only)")]
+    pub no_cache: Option<bool>,
+    #[schemars(
+        description = "Days without a commit before a project counts as stale (default 90)"
+    )]
+    pub days: Option<u64>,
+    #[schemars(description = "git_remote sub-action: list (default) or add")]
+    pub remote_action: Option<String>,
+    #[schemars(description = "Remote name to add (git_remote add, e.g. 'upstream')")]
+    pub remote_name: Option<String>,
+    #[schemars(description = "Remote URL to add (git_remote add)")]
+    pub remote_url: Option<String>,
+    #[schemars(
+        description = "Return the full 'mix hex.outdated' output per project (outdated only)"
+    )]
+    pub raw: Option<bool>,
+    #[schemars(description = "dep_graph output: tree (default) or dot for Graphviz")]
+    pub graph_format: Option<String>,
+    #[schemars(
+        description = "Shell command for run_in_all; must be listed in ~/.config/steve/allowed_commands.toml"
+    )]
+    pub command: Option<String>,
+    #[schemars(description = "Also remove the _build directory (archive_deps only)")]
+    pub remove_build: Option<bool>,
 }
 
-pub async fn handle_elixir_projects(req: ElixirProjectsRequest) -> CallToolResult {
-    let is_refresh = req.action == "refresh";
-    let mut projects = get_elixir_projects(req.path.as_deref(), is_refresh);
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct MixNewRequest {
+    #[schemars(description = "Name of the new project")]
+    pub name: String,
+    #[schemars(description = "Directory to create the project in (defaults to ~/src/flt)")]
+    pub dest_dir: Option<String>,
+    #[schemars(description = "Project template: app (default), sup, or lib")]
+    pub template: Option<String>,
+    #[schemars(description = "OTP application name, if different from the project name")]
+    pub app_name: Option<String>,
+}
 
-    // Filter by project name if specified
-    if let Some(ref project_filter) = req.project {
-        let filter_lower = project_filter.to_lowercase();
-        projects.retain(|p| {
-            p.file_name()
-                .map(|n| n.to_string_lossy().to_lowercase().contains(&filter_lower))
-                .unwrap_or(false)
-        });
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct PhxNewRequest {
+    #[schemars(description = "Name of the new Phoenix application")]
+    pub name: String,
+    #[schemars(description = "Directory to create the project in (defaults to ~/src/flt)")]
+    pub dest_dir: Option<String>,
+    #[schemars(description = "Skip Ecto (--no-ecto)")]
+    pub no_ecto: Option<bool>,
+    #[schemars(description = "Skip LiveView (--no-live)")]
+    pub no_live: Option<bool>,
+    #[schemars(description = "Skip asset tooling (--no-assets)")]
+    pub no_assets: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct MixTaskRequest {
+    #[schemars(description = "Project name, matched against the project cache")]
+    pub project: String,
+    #[schemars(description = "Mix task to run, e.g. 'ecto.migrate' or 'phx.routes'")]
+    pub task: String,
+    #[schemars(description = "Arguments passed to the task")]
+    pub args: Option<Vec<String>>,
+    #[schemars(description = "Extra environment variables, e.g. {\"MIX_ENV\": \"test\"}")]
+    pub env: Option<HashMap<String, String>>,
+    #[schemars(
+        description = "Seconds before the task is killed (defaults to command_timeout_secs)"
+    )]
+    pub timeout_secs: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct IexEvalRequest {
+    #[schemars(description = "Project name, matched against the project cache")]
+    pub project: String,
+    #[schemars(description = "Elixir expression to evaluate (max 500 characters)")]
+    pub expression: String,
+    #[schemars(description = "Seconds before evaluation is killed (default and max 30)")]
+    pub timeout_secs: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct HexSearchRequest {
+    #[schemars(description = "Search term")]
+    pub query: String,
+    #[schemars(description = "Maximum number of packages to return (default 10)")]
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AllowedCommands {
+    commands: Vec<String>,
+}
+
+/// Format of the project cache file, recorded as a `# version=N` first line.
+/// Version 1 files have no header.
+const CACHE_VERSION: u32 = 2;
+
+/// How many directory levels below the root are scanned for projects
+const DEFAULT_SCAN_DEPTH: usize = 8;
+
+/// Local environment files checked by check_env, relative to the project root.
+const ENV_FILES: [&str; 4] = [".env", ".env.local", ".env.dev", "config/.env"];
+
+// Default directories to skip (dependencies, build artifacts, etc.)
+const SKIP_DIRS: [&str; 6] = [
+    "deps",
+    "_build",
+    ".elixir_ls",
+    "node_modules",
+    ".git",
+    "_checkouts",
+];
+
+struct ProjectWatcher {
+    root: PathBuf,
+}
+
+static PROJECT_WATCHER: Mutex<Option<ProjectWatcher>> = Mutex::new(None);
+static WATCHER_STARTED: Once = Once::new();
+
+/// Quiet period after the last project change before the cache is updated, so
+/// a checkout or `mix new` touching many files causes a single write.
+const WATCH_DEBOUNCE: Duration = Duration::from_millis(1500);
+
+/// Start watching the default scan root for added or removed `mix.exs` files,
+/// updating the project cache whenever one changes. Setting up the watches
+/// walks the whole tree, so it happens on a background thread.
+pub fn start_project_watcher() {
+    WATCHER_STARTED.call_once(|| {
+        let _ = std::thread::Builder::new()
+            .name("project-watcher".to_string())
+            .spawn(run_project_watcher);
+    });
+}
+
+fn run_project_watcher() {
+    let root = default_scan_root();
+    if !root.exists() {
+        return;
+    }
+
+    let (sender, events) = mpsc::channel();
+    let Ok(mut watcher) = notify::recommended_watcher(sender) else {
+        return;
+    };
+    let skip_dirs = resolve_skip_dirs(None);
+    let scan_depth = resolve_scan_depth(None);
+
+    watch_tree(&mut watcher, &root, &skip_dirs, scan_depth);
+    *PROJECT_WATCHER.lock().unwrap_or_else(|e| e.into_inner()) = Some(ProjectWatcher { root });
+
+    let mut changes = ProjectChanges::default();
+    loop {
+        let event = if changes.is_empty() {
+            match events.recv() {
+                Ok(event) => event,
+                Err(_) => break,
+            }
+        } else {
+            match events.recv_timeout(WATCH_DEBOUNCE) {
+                Ok(event) => event,
+                Err(RecvTimeoutError::Timeout) => {
+                    std::mem::take(&mut changes).apply();
+                    continue;
+                }
+                Err(RecvTimeoutError::Disconnected) => break,
+            }
+        };
+        let Ok(event) = event else {
+            continue;
+        };
+        if !matches!(
+            event.kind,
+            EventKind::Create(_) | EventKind::Remove(_) | EventKind::Modify(ModifyKind::Name(_))
+        ) {
+            continue;
+        }
+
+        for path in &event.paths {
+            if path.file_name().is_some_and(|n| n == "mix.exs") {
+                if let Some(project) = path.parent()
+                    && !is_skipped(path, &skip_dirs)
+                {
+                    changes.project_changed(project);
+                }
+            } else if !path.exists() {
+                // A removed directory, or one renamed away, takes its projects along
+                changes.removed_dirs.insert(path.clone());
+            } else if path.is_dir() && !is_skipped(path, &skip_dirs) {
+                // Directories are watched one by one, so new ones need their
+                // own watch. A project created before the watch was added is
+                // only noticed by looking for its mix.exs.
+                changes
+                    .added
+                    .extend(watch_tree(&mut watcher, path, &skip_dirs, scan_depth));
+            }
+        }
+    }
+}
+
+/// Project changes seen by the watcher since the cache was last updated.
+/// Applying them edits the cached list in place, so projects cached from a
+/// custom refresh are kept.
+#[derive(Default)]
+struct ProjectChanges {
+    added: HashSet<PathBuf>,
+    removed: HashSet<PathBuf>,
+    removed_dirs: HashSet<PathBuf>,
+}
+
+impl ProjectChanges {
+    fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.removed_dirs.is_empty()
+    }
+
+    /// Record that the mix.exs of `project` was created or removed.
+    fn project_changed(&mut self, project: &Path) {
+        if project.join("mix.exs").exists() {
+            self.removed.remove(project);
+            self.added.insert(project.to_path_buf());
+        } else {
+            self.added.remove(project);
+            self.removed.insert(project.to_path_buf());
+        }
+    }
+
+    fn apply(self) {
+        let Some(cached) = load_projects_from_cache() else {
+            if let Ok(projects) = scan_elixir_projects(None, None, None, None) {
+                let _ = save_projects_to_cache(&projects);
+            }
+            return;
+        };
+
+        let mut projects: Vec<PathBuf> = cached
+            .iter()
+            .filter(|p| {
+                !self.removed.contains(*p) && !self.removed_dirs.iter().any(|d| p.starts_with(d))
+            })
+            .cloned()
+            .collect();
+        for project in self.added {
+            if project.join("mix.exs").exists() && !projects.contains(&project) {
+                projects.push(project);
+            }
+        }
+        projects.sort();
+
+        if projects != cached {
+            let _ = save_projects_to_cache(&projects);
+        }
+    }
+}
+
+/// Watch `dir` and every directory below it that a scan would visit, without
+/// descending into skip dirs such as deps and _build. Returns the projects
+/// found along the way.
+fn watch_tree(
+    watcher: &mut RecommendedWatcher,
+    dir: &Path,
+    skip_dirs: &HashSet<String>,
+    scan_depth: usize,
+) -> Vec<PathBuf> {
+    let mut projects = Vec::new();
+    let entries = WalkDir::new(dir)
+        .max_depth(scan_depth + 1)
+        .into_iter()
+        .filter_entry(|e| {
+            !(e.file_type().is_dir()
+                && skip_dirs.contains(e.file_name().to_string_lossy().as_ref()))
+        })
+        .filter_map(|e| e.ok());
+    for entry in entries {
+        if entry.file_type().is_dir() {
+            let _ = watcher.watch(entry.path(), RecursiveMode::NonRecursive);
+        } else if entry.file_name() == "mix.exs"
+            && let Some(project) = entry.path().parent()
+        {
+            projects.push(project.to_path_buf());
+        }
+    }
+    projects
+}
+
+fn is_skipped(path: &Path, skip_dirs: &HashSet<String>) -> bool {
+    path.components().any(|c| {
+        c.as_os_str()
+            .to_str()
+            .is_some_and(|name| skip_dirs.contains(name))
+    })
+}
+
+/// Project lookups in flight, keyed by a hash of the action, path and skip
+/// dirs. A second identical call waits for the first one's result instead of
+/// walking the same tree again.
+pub type ProjectScans =
+    Mutex<HashMap<String, Arc<watch::Receiver<Option<Result<Vec<PathBuf>, String>>>>>>;
+
+pub async fn handle_elixir_projects(
+    req: ElixirProjectsRequest,
+    scans: &ProjectScans,
+) -> CallToolResult {
+    let started = Instant::now();
+    let scanned = match get_elixir_projects_shared(scans, &req).await {
+        Ok(projects) => projects,
+        Err(e) => return CallToolResult::success(vec![Content::text(e)]),
+    };
+    let elapsed = started.elapsed();
+    let scanned_count = scanned.len();
+    let unignored = filter_ignored(scanned);
+    let ignored_count = scanned_count - unignored.len();
+    let projects = filter_projects(unignored, req.project.as_deref());
+
+    match req.action {
+        ElixirProjectsAction::Refresh => handle_refresh(projects, elapsed, ignored_count),
+        ElixirProjectsAction::List => handle_list(projects),
+        ElixirProjectsAction::UpdateDeps => handle_update_deps(projects, &req).await,
+        ElixirProjectsAction::Outdated => handle_outdated(projects, &req).await,
+        ElixirProjectsAction::GitPull => handle_git_pull(projects, &req).await,
+        ElixirProjectsAction::GitPush => handle_git_push(projects).await,
+        ElixirProjectsAction::GitStatus => handle_git_status(projects).await,
+        ElixirProjectsAction::Delete => handle_delete(projects, &req),
+        ElixirProjectsAction::Ignore => handle_ignore(&req),
+        ElixirProjectsAction::Unignore => handle_unignore(&req),
+        ElixirProjectsAction::Watch => handle_watch(),
+        ElixirProjectsAction::PreCommitCheck => handle_pre_commit_check(projects).await,
+        ElixirProjectsAction::DepsTree => handle_deps_tree(projects, &req).await,
+        ElixirProjectsAction::Xref => handle_xref(projects, &req).await,
+        ElixirProjectsAction::EctoReset => handle_ecto_reset(projects, &req).await,
+        ElixirProjectsAction::GitPushForceWithLease => {
+            handle_git_push_force_with_lease(projects, &req).await
+        }
+        ElixirProjectsAction::GitCommit => handle_git_commit(projects, &req).await,
+        ElixirProjectsAction::GitAdd => handle_git_add(projects, &req).await,
+        ElixirProjectsAction::Test => handle_test(projects, &req).await,
+        ElixirProjectsAction::ChangelogSummary => handle_changelog_summary(projects),
+        ElixirProjectsAction::VersionBump => handle_version_bump(projects, &req),
+        ElixirProjectsAction::Audit => handle_audit(projects).await,
+        ElixirProjectsAction::Coverage => handle_coverage(projects, &req).await,
+        ElixirProjectsAction::Import => handle_import(&req),
+        ElixirProjectsAction::Export => handle_export(projects, &req),
+        ElixirProjectsAction::GitFetch => handle_git_fetch(projects).await,
+        ElixirProjectsAction::Rebase => handle_rebase(projects).await,
+        ElixirProjectsAction::LoadTest => handle_load_test(projects, &req).await,
+        ElixirProjectsAction::HealthCheck => handle_health_check(projects).await,
+        ElixirProjectsAction::DockerBuild => handle_docker_build(projects, &req).await,
+        ElixirProjectsAction::MiseCheck => handle_mise_check(projects).await,
+        ElixirProjectsAction::NixInfo => handle_nix_info(projects).await,
+        ElixirProjectsAction::NixBuild => handle_nix_build(projects).await,
+        ElixirProjectsAction::DepsGet => handle_deps_get(projects).await,
+        ElixirProjectsAction::CompileWarnings => handle_compile_warnings(projects).await,
+        ElixirProjectsAction::Stale => handle_stale(projects, &req).await,
+        ElixirProjectsAction::GitRemote => handle_git_remote(projects, &req).await,
+        ElixirProjectsAction::Summary => handle_summary(projects).await,
+        ElixirProjectsAction::DepGraph => handle_dep_graph(projects, &req),
+        ElixirProjectsAction::LockDiff => handle_lock_diff(projects).await,
+        ElixirProjectsAction::RunInAll => handle_run_in_all(projects, &req).await,
+        ElixirProjectsAction::VersionMatrix => handle_version_matrix(projects).await,
+        ElixirProjectsAction::GitSquashLocal => handle_git_squash_local(projects, &req).await,
+        ElixirProjectsAction::CoverageBadge => handle_coverage_badge(projects).await,
+        ElixirProjectsAction::PortConflicts => handle_port_conflicts(projects),
+        ElixirProjectsAction::ArchiveDeps => handle_archive_deps(projects, &req).await,
+        ElixirProjectsAction::CheckEnv => handle_check_env(projects),
+    }
+}
+
+pub async fn handle_mix_new(req: MixNewRequest) -> CallToolResult {
+    let mut args = vec!["new", req.name.as_str()];
+    match req.template.as_deref().unwrap_or("app") {
+        "app" | "lib" => {}
+        "sup" => args.push("--sup"),
+        other => {
+            return CallToolResult::success(vec![Content::text(format!(
+                "Unknown template '{}'. Use: app, sup, lib",
+                other
+            ))]);
+        }
+    }
+    if let Some(ref app_name) = req.app_name {
+        args.extend(["--app", app_name]);
+    }
+
+    let dest_dir = req
+        .dest_dir
+        .as_deref()
+        .map(expand_path)
+        .unwrap_or_else(default_scan_root);
+
+    if let Err(e) = fs::create_dir_all(&dest_dir) {
+        return CallToolResult::success(vec![Content::text(format!(
+            "✗ Failed to create {}: {}",
+            dest_dir.display(),
+            e
+        ))]);
+    }
+
+    let output = Command::new("mix")
+        .args(&args)
+        .current_dir(&dest_dir)
+        .cancellable_output()
+        .await;
+
+    let text = match output {
+        Ok(o) if o.status.success() => {
+            if let Ok(projects) = scan_elixir_projects(None, None, None, None) {
+                let _ = save_projects_to_cache(&projects);
+            }
+            format!(
+                "✓ Created {} in {}\n{}",
+                req.name,
+                dest_dir.display(),
+                String::from_utf8_lossy(&o.stdout).trim_end()
+            )
+        }
+        Ok(o) => format!("✗ mix new failed: {}", first_error_line(&o)),
+        Err(e) => format!("✗ Failed to run mix: {}", e),
+    };
+
+    CallToolResult::success(vec![Content::text(text)])
+}
+
+pub async fn handle_phx_new(req: PhxNewRequest) -> CallToolResult {
+    let dest_dir = req
+        .dest_dir
+        .as_deref()
+        .map(expand_path)
+        .unwrap_or_else(default_scan_root);
+
+    if let Err(e) = fs::create_dir_all(&dest_dir) {
+        return CallToolResult::success(vec![Content::text(format!(
+            "✗ Failed to create {}: {}",
+            dest_dir.display(),
+            e
+        ))]);
+    }
+
+    // --no-install skips the interactive "Fetch and install dependencies?" prompt
+    let mut cmd = Command::new("mix");
+    cmd.arg("phx.new").arg("--no-install").arg(&req.name);
+    if req.no_ecto.unwrap_or(false) {
+        cmd.arg("--no-ecto");
+    }
+    if req.no_live.unwrap_or(false) {
+        cmd.arg("--no-live");
+    }
+    if req.no_assets.unwrap_or(false) {
+        cmd.arg("--no-assets");
+    }
+
+    let output = cmd.current_dir(&dest_dir).cancellable_output().await;
+
+    let text = match output {
+        Ok(o) if o.status.success() => {
+            let project_path = dest_dir.join(&req.name);
+            let mut projects = load_projects_from_cache().unwrap_or_default();
+            if !projects.contains(&project_path) {
+                projects.push(project_path.clone());
+                projects.sort();
+                let _ = save_projects_to_cache(&projects);
+            }
+            format!(
+                "✓ Created {}\n{}",
+                project_path.display(),
+                String::from_utf8_lossy(&o.stdout).trim_end()
+            )
+        }
+        Ok(o) => format!("✗ mix phx.new failed: {}", first_error_line(&o)),
+        Err(e) => format!("✗ Failed to run mix: {}", e),
+    };
+
+    CallToolResult::success(vec![Content::text(text)])
+}
+
+pub async fn handle_mix_task(req: MixTaskRequest) -> CallToolResult {
+    const MAX_OUTPUT: usize = 8 * 1024;
+
+    let valid_task = !req.task.is_empty()
+        && req
+            .task
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-'));
+    if !valid_task {
+        return CallToolResult::success(vec![Content::text(format!(
+            "✗ Invalid task '{}': only letters, digits, '.', '_' and '-' are allowed",
+            req.task
+        ))]);
+    }
+
+    let project = match resolve_project(&req.project) {
+        Ok(project) => project,
+        Err(e) => return CallToolResult::success(vec![Content::text(e)]),
+    };
+
+    let timeout = Duration::from_secs(
+        req.timeout_secs
+            .unwrap_or_else(|| load_config().command_timeout_secs),
+    );
+    let mut cmd = Command::new("mix");
+    cmd.arg(&req.task)
+        .args(req.args.iter().flatten())
+        .envs(req.env.iter().flatten())
+        .current_dir(&project);
+
+    let text = match run_with_timeout(&mut cmd, timeout).await {
+        Ok(o) => {
+            let mut combined = format!(
+                "{}\n{}",
+                String::from_utf8_lossy(&o.stdout).trim_end(),
+                String::from_utf8_lossy(&o.stderr).trim_end()
+            );
+            if combined.len() > MAX_OUTPUT {
+                let mut end = MAX_OUTPUT;
+                while !combined.is_char_boundary(end) {
+                    end -= 1;
+                }
+                combined.truncate(end);
+                combined.push_str("\n... (truncated)");
+            }
+            let status = if o.status.success() { "✓" } else { "✗" };
+            format!(
+                "{} mix {} in {}\n{}",
+                status,
+                req.task,
+                project.display(),
+                combined.trim()
+            )
+        }
+        Err(e) => format!("✗ mix {} in {}: {}", req.task, project.display(), e),
+    };
+
+    CallToolResult::success(vec![Content::text(text)])
+}
+
+pub async fn handle_iex_eval(req: IexEvalRequest) -> CallToolResult {
+    const MAX_EXPRESSION: usize = 500;
+    const MAX_TIMEOUT_SECS: u64 = 30;
+
+    if req.expression.chars().count() > MAX_EXPRESSION {
+        return CallToolResult::success(vec![Content::text(format!(
+            "✗ Expression is longer than {} characters",
+            MAX_EXPRESSION
+        ))]);
     }
 
-    match req.action.as_str() {
-        "refresh" => handle_refresh(projects),
-        "list" => handle_list(projects),
-        "update_deps" => handle_update_deps(projects).await,
-        "outdated" => handle_outdated(projects).await,
-        "git_pull" => handle_git_pull(projects).await,
-        "git_push" => handle_git_push(projects).await,
-        "git_status" => handle_git_status(projects).await,
-        "delete" => handle_delete(projects, &req),
-        "ignore" => handle_ignore(&req),
-        "unignore" => handle_unignore(&req),
-        _ => CallToolResult::success(vec![Content::text(format!(
-            "Unknown action '{}'. Use: list, update_deps, outdated, git_pull, git_push, git_status, refresh, delete, ignore, unignore",
-            req.action
-        ))]),
+    let project = match resolve_project(&req.project) {
+        Ok(project) => project,
+        Err(e) => return CallToolResult::success(vec![Content::text(e)]),
+    };
+
+    // The expression goes in as a single argv entry, so no shell quoting is
+    // involved. Newlines let it span several statements.
+    let script = format!("IO.puts(inspect((\n{}\n), pretty: true))", req.expression);
+    let timeout = Duration::from_secs(
+        req.timeout_secs
+            .unwrap_or(MAX_TIMEOUT_SECS)
+            .min(MAX_TIMEOUT_SECS),
+    );
+    let mut cmd = Command::new("mix");
+    cmd.args(["run", "--no-start", "-e", &script])
+        .current_dir(&project);
+
+    let text = match run_with_timeout(&mut cmd, timeout).await {
+        Ok(o) if o.status.success() => String::from_utf8_lossy(&o.stdout).trim_end().to_string(),
+        Ok(o) => format!(
+            "✗ Evaluation failed:\n{}\n{}",
+            String::from_utf8_lossy(&o.stdout).trim_end(),
+            String::from_utf8_lossy(&o.stderr).trim_end()
+        )
+        .trim_end()
+        .to_string(),
+        Err(e) => format!("✗ mix run in {}: {}", project.display(), e),
+    };
+
+    CallToolResult::success(vec![Content::text(text)])
+}
+
+pub async fn handle_hex_search(req: HexSearchRequest) -> CallToolResult {
+    let limit = req.limit.unwrap_or(10).max(1);
+
+    // hex.search runs anywhere, but a project directory picks up its Hex config
+    let mut cmd = Command::new("mix");
+    cmd.args(["hex.search", &req.query]);
+    if let Some(project) = find_elixir_projects(None).first() {
+        cmd.current_dir(project);
     }
+
+    let output = match cmd.cancellable_output().await {
+        Ok(o) if o.status.success() => String::from_utf8_lossy(&o.stdout).to_string(),
+        Ok(o) => {
+            return CallToolResult::success(vec![Content::text(format!(
+                "✗ mix hex.search failed: {}",
+                first_error_line(&o)
+            ))]);
+        }
+        Err(e) => {
+            return CallToolResult::success(vec![Content::text(format!(
+                "✗ Failed to run mix: {}",
+                e
+            ))]);
+        }
+    };
+
+    let packages = parse_hex_search(&output);
+    let text = if packages.is_empty() {
+        format!("No packages found for '{}'", req.query)
+    } else {
+        let lines: Vec<String> = packages
+            .iter()
+            .take(limit)
+            .map(|p| {
+                let mut line = format!("{} {}", p.get("package"), p.get("version"));
+                if !p.get("downloads").is_empty() {
+                    line.push_str(&format!(" ({} downloads)", p.get("downloads")));
+                }
+                if !p.get("description").is_empty() {
+                    line.push_str(&format!(" - {}", p.get("description")));
+                }
+                line
+            })
+            .collect();
+        format!(
+            "{} of {} packages for '{}':\n{}",
+            lines.len(),
+            packages.len(),
+            req.query,
+            lines.join("\n")
+        )
+    };
+
+    CallToolResult::success(vec![Content::text(text)])
 }
 
-fn handle_refresh(projects: Vec<PathBuf>) -> CallToolResult {
+fn handle_refresh(projects: Vec<PathBuf>, elapsed: Duration, ignored: usize) -> CallToolResult {
     let output = format!(
-        "Refreshed project cache. Found {} Elixir projects:\n{}",
+        "Refreshed in {:.2}s. Found {} Elixir projects ({} skipped by ignore list):\n{}",
+        elapsed.as_secs_f64(),
         projects.len(),
+        ignored,
         projects
             .iter()
             .map(|p| p.display().to_string())
@@ -72,9 +798,21 @@ fn handle_list(projects: Vec<PathBuf>) -> CallToolResult {
         let names: Vec<String> = projects
             .iter()
             .map(|p| {
-                p.file_name()
+                let name = p
+                    .file_name()
                     .map(|n| n.to_string_lossy().to_string())
-                    .unwrap_or_else(|| p.display().to_string())
+                    .unwrap_or_else(|| p.display().to_string());
+                // Runtime versions pinned for asdf, e.g. "elixir 1.16.2-otp-26"
+                let runtimes: Vec<String> = read_tool_versions(p)
+                    .into_iter()
+                    .filter(|(tool, _)| tool == "elixir" || tool == "erlang")
+                    .map(|(tool, version)| format!("{} {}", tool, version))
+                    .collect();
+                if runtimes.is_empty() {
+                    name
+                } else {
+                    format!("{} ({})", name, runtimes.join(", "))
+                }
             })
             .collect();
         let output = format!("Found {} projects: {}", projects.len(), names.join(", "));
@@ -82,22 +820,27 @@ fn handle_list(projects: Vec<PathBuf>) -> CallToolResult {
     }
 }
 
-async fn handle_update_deps(projects: Vec<PathBuf>) -> CallToolResult {
+async fn handle_update_deps(projects: Vec<PathBuf>, req: &ElixirProjectsRequest) -> CallToolResult {
     if projects.is_empty() {
         return CallToolResult::success(vec![Content::text(
             "No Elixir projects found".to_string(),
         )]);
     }
 
+    let verbose = req.verbose.unwrap_or(false);
     let mut results: Vec<String> = Vec::new();
     for project in &projects {
+        if shutdown_token().is_cancelled() {
+            results.push("- stopped: shutting down".to_string());
+            break;
+        }
         let output = Command::new("mix")
             .args(["deps.update", "--all"])
             .current_dir(project)
-            .output()
+            .cancellable_output()
             .await;
 
-        let status = match output {
+        let status = match &output {
             Ok(o) if o.status.success() => "✓".to_string(),
             Ok(o) => {
                 let stderr = String::from_utf8_lossy(&o.stderr);
@@ -106,6 +849,9 @@ async fn handle_update_deps(projects: Vec<PathBuf>) -> CallToolResult {
             Err(e) => format!("✗ {}", e),
         };
         results.push(format!("{} {}", status, project.display()));
+        if verbose && let Ok(o) = &output {
+            results.push(verbose_details(o));
+        }
     }
 
     CallToolResult::success(vec![Content::text(format!(
@@ -115,21 +861,28 @@ async fn handle_update_deps(projects: Vec<PathBuf>) -> CallToolResult {
     ))])
 }
 
-async fn handle_outdated(projects: Vec<PathBuf>) -> CallToolResult {
+async fn handle_outdated(projects: Vec<PathBuf>, req: &ElixirProjectsRequest) -> CallToolResult {
     if projects.is_empty() {
         return CallToolResult::success(vec![Content::text(
             "No Elixir projects found".to_string(),
         )]);
     }
+    if req.raw.unwrap_or(false) {
+        return handle_outdated_raw(projects).await;
+    }
 
     let mut results: Vec<String> = Vec::new();
     let mut projects_with_outdated = 0;
 
     for project in &projects {
+        if shutdown_token().is_cancelled() {
+            results.push("- stopped: shutting down".to_string());
+            break;
+        }
         let output = Command::new("mix")
             .args(["hex.outdated"])
             .current_dir(project)
-            .output()
+            .cancellable_output()
             .await;
 
         let project_name = project
@@ -184,19 +937,62 @@ async fn handle_outdated(projects: Vec<PathBuf>) -> CallToolResult {
     CallToolResult::success(vec![Content::text(summary)])
 }
 
-async fn handle_git_pull(projects: Vec<PathBuf>) -> CallToolResult {
+/// `mix hex.outdated` output for each project, verbatim under a header.
+async fn handle_outdated_raw(projects: Vec<PathBuf>) -> CallToolResult {
+    let mut sections: Vec<String> = Vec::new();
+    for project in &projects {
+        if shutdown_token().is_cancelled() {
+            sections.push("- stopped: shutting down".to_string());
+            break;
+        }
+        let output = Command::new("mix")
+            .args(["hex.outdated"])
+            .current_dir(project)
+            .cancellable_output()
+            .await;
+
+        let project_name = project
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| project.display().to_string());
+
+        // Exit code 1 only means something is outdated, so always show stdout
+        let body = match output {
+            Ok(o) if o.stdout.is_empty() => format!("✗ {}", first_error_line(&o)),
+            Ok(o) => String::from_utf8_lossy(&o.stdout).trim_end().to_string(),
+            Err(e) => format!("✗ {}", e),
+        };
+        sections.push(format!("## {}\n{}", project_name, body));
+    }
+
+    CallToolResult::success(vec![Content::text(sections.join("\n\n"))])
+}
+
+async fn handle_git_pull(projects: Vec<PathBuf>, req: &ElixirProjectsRequest) -> CallToolResult {
     if projects.is_empty() {
         return CallToolResult::success(vec![Content::text(
             "No Elixir projects found".to_string(),
         )]);
     }
 
+    let rebase = req.rebase.unwrap_or(false);
+    let verbose = req.verbose.unwrap_or(false);
+    let args: &[&str] = if rebase {
+        &["pull", "--rebase", "--autostash"]
+    } else {
+        &["pull"]
+    };
+
     let mut results: Vec<String> = Vec::new();
     for project in &projects {
+        if shutdown_token().is_cancelled() {
+            results.push("- stopped: shutting down".to_string());
+            break;
+        }
         let output = Command::new("git")
-            .args(["pull"])
+            .args(args)
             .current_dir(project)
-            .output()
+            .cancellable_output()
             .await;
 
         let project_name = project
@@ -204,11 +1000,17 @@ async fn handle_git_pull(projects: Vec<PathBuf>) -> CallToolResult {
             .map(|n| n.to_string_lossy().to_string())
             .unwrap_or_else(|| project.display().to_string());
 
-        let status = match output {
+        let status = match &output {
             Ok(o) if o.status.success() => {
                 let stdout = String::from_utf8_lossy(&o.stdout);
+                let stderr = String::from_utf8_lossy(&o.stderr);
                 if stdout.contains("Already up to date") {
                     "✓ (up to date)".to_string()
+                } else if rebase
+                    && (stdout.contains("Successfully rebased")
+                        || stderr.contains("Successfully rebased"))
+                {
+                    "✓ (rebased)".to_string()
                 } else {
                     "✓ (updated)".to_string()
                 }
@@ -220,16 +1022,20 @@ async fn handle_git_pull(projects: Vec<PathBuf>) -> CallToolResult {
             Err(e) => format!("✗ {}", e),
         };
         results.push(format!("{} {}", project_name, status));
+        if verbose && let Ok(o) = &output {
+            results.push(verbose_details(o));
+        }
     }
 
     CallToolResult::success(vec![Content::text(format!(
-        "Git pull on {} projects:\n{}",
+        "Git pull{} on {} projects:\n{}",
+        if rebase { " --rebase" } else { "" },
         projects.len(),
         results.join("\n")
     ))])
 }
 
-async fn handle_git_push(projects: Vec<PathBuf>) -> CallToolResult {
+async fn handle_git_fetch(projects: Vec<PathBuf>) -> CallToolResult {
     if projects.is_empty() {
         return CallToolResult::success(vec![Content::text(
             "No Elixir projects found".to_string(),
@@ -238,10 +1044,14 @@ async fn handle_git_push(projects: Vec<PathBuf>) -> CallToolResult {
 
     let mut results: Vec<String> = Vec::new();
     for project in &projects {
+        if shutdown_token().is_cancelled() {
+            results.push("- stopped: shutting down".to_string());
+            break;
+        }
         let output = Command::new("git")
-            .args(["push"])
+            .args(["fetch", "--all", "--prune"])
             .current_dir(project)
-            .output()
+            .cancellable_output()
             .await;
 
         let project_name = project
@@ -251,11 +1061,13 @@ async fn handle_git_push(projects: Vec<PathBuf>) -> CallToolResult {
 
         let status = match output {
             Ok(o) if o.status.success() => {
+                // Updated refs are reported on stderr, e.g. "   abc..def  main -> origin/main"
                 let stderr = String::from_utf8_lossy(&o.stderr);
-                if stderr.contains("Everything up-to-date") {
+                let updated = stderr.lines().filter(|line| line.contains("->")).count();
+                if updated == 0 {
                     "✓ (up to date)".to_string()
                 } else {
-                    "✓ (pushed)".to_string()
+                    format!("✓ ({} refs updated)", updated)
                 }
             }
             Ok(o) => {
@@ -268,69 +1080,225 @@ async fn handle_git_push(projects: Vec<PathBuf>) -> CallToolResult {
     }
 
     CallToolResult::success(vec![Content::text(format!(
-        "Git push on {} projects:\n{}",
+        "Git fetch on {} projects:\n{}",
         projects.len(),
         results.join("\n")
     ))])
 }
 
-async fn handle_git_status(projects: Vec<PathBuf>) -> CallToolResult {
+async fn handle_rebase(projects: Vec<PathBuf>) -> CallToolResult {
     if projects.is_empty() {
         return CallToolResult::success(vec![Content::text(
             "No Elixir projects found".to_string(),
         )]);
     }
 
-    let mut dirty_projects: Vec<String> = Vec::new();
-    let mut ahead_projects: Vec<String> = Vec::new();
-    let mut clean_count = 0;
-
+    let mut results: Vec<String> = Vec::new();
     for project in &projects {
+        if shutdown_token().is_cancelled() {
+            results.push("- stopped: shutting down".to_string());
+            break;
+        }
         let project_name = project
             .file_name()
             .map(|n| n.to_string_lossy().to_string())
             .unwrap_or_else(|| project.display().to_string());
 
-        // Check for uncommitted changes
-        let status_output = Command::new("git")
-            .args(["status", "--porcelain"])
+        let Some(branch) = git_stdout(project, &["rev-parse", "--abbrev-ref", "HEAD"]).await else {
+            results.push(format!(
+                "{} ✗ could not determine current branch",
+                project_name
+            ));
+            continue;
+        };
+        let upstream = format!("origin/{}", branch);
+
+        let local_commits = git_stdout(
+            project,
+            &["rev-list", "--count", &format!("{}..HEAD", upstream)],
+        )
+        .await
+        .and_then(|count| count.parse::<u32>().ok())
+        .unwrap_or(0);
+
+        let output = Command::new("git")
+            .args(["rebase", "--autostash", &upstream])
             .current_dir(project)
-            .output()
+            .cancellable_output()
             .await;
 
-        let has_changes = match &status_output {
-            Ok(o) => !o.stdout.is_empty(),
-            Err(_) => false,
-        };
+        let status = match output {
+            Ok(o) if o.status.success() => {
+                let stdout = String::from_utf8_lossy(&o.stdout);
+                let stderr = String::from_utf8_lossy(&o.stderr);
+                let autostash = if stderr.contains("Applied autostash") {
+                    ", autostash applied"
+                } else {
+                    ""
+                };
+                if stdout.contains("is up to date") || stderr.contains("is up to date") {
+                    format!("✓ (up to date{})", autostash)
+                } else {
+                    format!(
+                        "✓ (rebased {} commits onto {}{})",
+                        local_commits, upstream, autostash
+                    )
+                }
+            }
+            Ok(o) => {
+                let _ = Command::new("git")
+                    .args(["rebase", "--abort"])
+                    .current_dir(project)
+                    .cancellable_output()
+                    .await;
+                format!("✗ aborted: {}", first_error_line(&o))
+            }
+            Err(e) => format!("✗ {}", e),
+        };
+        results.push(format!("{} {}", project_name, status));
+    }
 
-        // Check if ahead of remote
-        let ahead_output = Command::new("git")
-            .args(["status", "--branch", "--porcelain=v2"])
+    CallToolResult::success(vec![Content::text(format!(
+        "Git rebase on {} projects:\n{}",
+        projects.len(),
+        results.join("\n")
+    ))])
+}
+
+async fn handle_git_push(projects: Vec<PathBuf>) -> CallToolResult {
+    if projects.is_empty() {
+        return CallToolResult::success(vec![Content::text(
+            "No Elixir projects found".to_string(),
+        )]);
+    }
+
+    let mut results: Vec<String> = Vec::new();
+    for project in &projects {
+        if shutdown_token().is_cancelled() {
+            results.push("- stopped: shutting down".to_string());
+            break;
+        }
+        let output = Command::new("git")
+            .args(["push"])
             .current_dir(project)
-            .output()
+            .cancellable_output()
             .await;
 
-        let is_ahead = match &ahead_output {
+        let project_name = project
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| project.display().to_string());
+
+        let status = match output {
+            Ok(o) if o.status.success() => {
+                let stderr = String::from_utf8_lossy(&o.stderr);
+                if stderr.contains("Everything up-to-date") {
+                    "✓ (up to date)".to_string()
+                } else {
+                    "✓ (pushed)".to_string()
+                }
+            }
             Ok(o) => {
-                let stdout = String::from_utf8_lossy(&o.stdout);
-                stdout.contains("ahead")
+                let stderr = String::from_utf8_lossy(&o.stderr);
+                format!("✗ {}", stderr.lines().next().unwrap_or("failed"))
             }
+            Err(e) => format!("✗ {}", e),
+        };
+        results.push(format!("{} {}", project_name, status));
+    }
+
+    CallToolResult::success(vec![Content::text(format!(
+        "Git push on {} projects:\n{}",
+        projects.len(),
+        results.join("\n")
+    ))])
+}
+
+async fn handle_git_status(projects: Vec<PathBuf>) -> CallToolResult {
+    if projects.is_empty() {
+        return CallToolResult::success(vec![Content::text(
+            "No Elixir projects found".to_string(),
+        )]);
+    }
+
+    let mut conflicted_projects: Vec<String> = Vec::new();
+    let mut dirty_projects: Vec<String> = Vec::new();
+    let mut ahead_projects: Vec<String> = Vec::new();
+    let mut behind_projects: Vec<String> = Vec::new();
+    let mut clean_count = 0;
+
+    for project in &projects {
+        if shutdown_token().is_cancelled() {
+            break;
+        }
+        let project_name = project
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| project.display().to_string());
+
+        // Check for uncommitted changes
+        let status_output = Command::new("git")
+            .args(["status", "--porcelain"])
+            .current_dir(project)
+            .cancellable_output()
+            .await;
+
+        let has_changes = match &status_output {
+            Ok(o) => !o.stdout.is_empty(),
             Err(_) => false,
         };
 
+        // Unmerged paths show up as DD, AU, UD, UA, DU, AA or UU
+        let has_conflicts = match &status_output {
+            Ok(o) => String::from_utf8_lossy(&o.stdout).lines().any(|line| {
+                matches!(
+                    line.get(..2),
+                    Some("DD" | "AU" | "UD" | "UA" | "DU" | "AA" | "UU")
+                )
+            }),
+            Err(_) => false,
+        };
+
+        // Check how far ahead/behind the remote we are ("# branch.ab +N -M")
+        let branch_output = Command::new("git")
+            .args(["status", "--branch", "--porcelain=v2"])
+            .current_dir(project)
+            .cancellable_output()
+            .await;
+
+        let (ahead, behind) = match &branch_output {
+            Ok(o) => parse_ahead_behind(&String::from_utf8_lossy(&o.stdout)),
+            Err(_) => (0, 0),
+        };
+        let counts = format!("{} [+{}/-{}]", project_name, ahead, behind);
+
+        if has_conflicts {
+            conflicted_projects.push(project_name.clone());
+        }
         if has_changes {
             dirty_projects.push(project_name.clone());
         }
-        if is_ahead {
-            ahead_projects.push(project_name.clone());
+        if ahead > 0 {
+            ahead_projects.push(counts.clone());
         }
-        if !has_changes && !is_ahead {
+        if behind > 0 {
+            behind_projects.push(counts);
+        }
+        if !has_changes && ahead == 0 && behind == 0 {
             clean_count += 1;
         }
     }
 
     let mut output = String::new();
 
+    if !conflicted_projects.is_empty() {
+        output.push_str(&format!(
+            "🚨 Merge conflicts ({}):\n  {}\n\n",
+            conflicted_projects.len(),
+            conflicted_projects.join("\n  ")
+        ));
+    }
+
     if !dirty_projects.is_empty() {
         output.push_str(&format!(
             "⚠️  Uncommitted changes ({}):\n  {}\n\n",
@@ -347,7 +1315,15 @@ async fn handle_git_status(projects: Vec<PathBuf>) -> CallToolResult {
         ));
     }
 
-    if dirty_projects.is_empty() && ahead_projects.is_empty() {
+    if !behind_projects.is_empty() {
+        output.push_str(&format!(
+            "📥 Behind remote ({}):\n  {}\n\n",
+            behind_projects.len(),
+            behind_projects.join("\n  ")
+        ));
+    }
+
+    if dirty_projects.is_empty() && ahead_projects.is_empty() && behind_projects.is_empty() {
         output = format!("✅ All {} projects are clean and pushed!", projects.len());
     } else {
         output.push_str(&format!("✓ {} projects clean", clean_count));
@@ -369,6 +1345,18 @@ fn handle_delete(projects: Vec<PathBuf>, req: &ElixirProjectsRequest) -> CallToo
         )]);
     }
 
+    if !req.confirm.unwrap_or(false) {
+        let preview: Vec<String> = projects
+            .iter()
+            .map(|p| format!("  {} ({})", p.display(), format_size(dir_size(p))))
+            .collect();
+        return CallToolResult::success(vec![Content::text(format!(
+            "Would delete {} projects:\n{}\n\nRe-run with confirm: true to delete.",
+            projects.len(),
+            preview.join("\n")
+        ))]);
+    }
+
     let mut results: Vec<String> = Vec::new();
     for project in &projects {
         let project_name = project
@@ -383,7 +1371,9 @@ fn handle_delete(projects: Vec<PathBuf>, req: &ElixirProjectsRequest) -> CallToo
     }
 
     // Refresh cache after deletion
-    let _ = save_projects_to_cache(&scan_elixir_projects(None));
+    if let Ok(projects) = scan_elixir_projects(None, None, None, None) {
+        let _ = save_projects_to_cache(&projects);
+    }
 
     CallToolResult::success(vec![Content::text(results.join("\n"))])
 }
@@ -413,7 +1403,15 @@ fn handle_ignore(req: &ElixirProjectsRequest) -> CallToolResult {
     let all_projects = if let Some(cached) = load_projects_from_cache() {
         cached
     } else {
-        scan_elixir_projects(req.path.as_deref())
+        match scan_elixir_projects(
+            req.path.as_deref(),
+            req.skip_dirs.as_deref(),
+            req.scan_depth,
+            req.ignore_glob.as_deref(),
+        ) {
+            Ok(projects) => projects,
+            Err(e) => return CallToolResult::success(vec![Content::text(e)]),
+        }
     };
 
     let filter = req.project.as_ref().unwrap().to_lowercase();
@@ -475,173 +1473,3483 @@ fn handle_unignore(req: &ElixirProjectsRequest) -> CallToolResult {
     ))])
 }
 
-// Helper functions
-
-fn get_cache_path() -> PathBuf {
-    dirs::home_dir()
-        .map(|h| h.join(".cache/steve/projects"))
-        .unwrap_or_else(|| PathBuf::from(".projects"))
-}
-
-fn get_ignore_path() -> PathBuf {
-    dirs::home_dir()
-        .map(|h| h.join(".cache/steve/ignored"))
-        .unwrap_or_else(|| PathBuf::from(".ignored"))
+fn handle_watch() -> CallToolResult {
+    let slot = PROJECT_WATCHER.lock().unwrap_or_else(|e| e.into_inner());
+    let output = match slot.as_ref() {
+        Some(watcher) => format!(
+            "Project watcher: running (watching {})",
+            watcher.root.display()
+        ),
+        None => "Project watcher: stopped".to_string(),
+    };
+    CallToolResult::success(vec![Content::text(output)])
 }
 
-fn load_ignored_projects() -> HashSet<String> {
-    let ignore_path = get_ignore_path();
-    if !ignore_path.exists() {
-        return HashSet::new();
+async fn handle_pre_commit_check(projects: Vec<PathBuf>) -> CallToolResult {
+    if projects.is_empty() {
+        return CallToolResult::success(vec![Content::text(
+            "No Elixir projects found".to_string(),
+        )]);
     }
 
-    fs::File::open(&ignore_path)
-        .ok()
-        .map(|file| BufReader::new(file).lines().map_while(Result::ok).collect())
-        .unwrap_or_default()
-}
+    let checks: [(&str, &[&str]); 4] = [
+        ("format", &["format", "--check-formatted"]),
+        ("credo", &["credo", "--strict"]),
+        ("compile", &["compile", "--warnings-as-errors"]),
+        ("test", &["test", "--exclude", "integration"]),
+    ];
+    let timeout = Duration::from_secs(load_config().command_timeout_secs);
 
-fn save_ignored_projects(ignored: &HashSet<String>) -> Result<(), std::io::Error> {
-    let ignore_path = get_ignore_path();
-    if let Some(parent) = ignore_path.parent() {
-        fs::create_dir_all(parent)?;
-    }
+    let mut results: Vec<String> = Vec::new();
+    let mut go_count = 0;
 
-    let mut file = fs::File::create(&ignore_path)?;
-    for name in ignored {
-        writeln!(file, "{}", name)?;
-    }
-    Ok(())
-}
+    for project in &projects {
+        if shutdown_token().is_cancelled() {
+            results.push("- stopped: shutting down".to_string());
+            break;
+        }
+        let project_name = project
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| project.display().to_string());
 
-fn load_projects_from_cache() -> Option<Vec<PathBuf>> {
-    let cache_path = get_cache_path();
-    if !cache_path.exists() {
-        return None;
-    }
+        let mut check_lines: Vec<String> = Vec::new();
+        let mut all_passed = true;
 
-    let file = fs::File::open(&cache_path).ok()?;
-    let reader = BufReader::new(file);
-    let mut projects: Vec<PathBuf> = Vec::new();
-    let mut needs_update = false;
+        for (name, args) in checks {
+            let output =
+                run_with_timeout(Command::new("mix").args(args).current_dir(project), timeout)
+                    .await;
 
-    for line in reader.lines().map_while(Result::ok) {
-        let path = PathBuf::from(&line);
-        if path.exists() && path.join("mix.exs").exists() {
-            projects.push(path);
-        } else {
-            needs_update = true;
+            match output {
+                Ok(o) if o.status.success() => check_lines.push(format!("✓ {}", name)),
+                Ok(o) => {
+                    all_passed = false;
+                    check_lines.push(format!("✗ {}: {}", name, first_error_line(&o)));
+                }
+                Err(e) => {
+                    all_passed = false;
+                    check_lines.push(format!("✗ {}: {}", name, e));
+                }
+            }
         }
-    }
 
-    // Update cache if we removed any stale entries
-    if needs_update {
-        let _ = save_projects_to_cache(&projects);
+        let verdict = if all_passed {
+            go_count += 1;
+            "✅ GO"
+        } else {
+            "⛔ NO-GO"
+        };
+        results.push(format!(
+            "{} {}\n  {}",
+            verdict,
+            project_name,
+            check_lines.join("\n  ")
+        ));
     }
 
-    Some(projects)
+    CallToolResult::success(vec![Content::text(format!(
+        "Pre-commit check on {} projects ({} ready):\n{}",
+        projects.len(),
+        go_count,
+        results.join("\n")
+    ))])
 }
 
-fn save_projects_to_cache(projects: &[PathBuf]) -> Result<(), std::io::Error> {
-    let cache_path = get_cache_path();
-    if let Some(parent) = cache_path.parent() {
-        fs::create_dir_all(parent)?;
+async fn handle_deps_tree(projects: Vec<PathBuf>, req: &ElixirProjectsRequest) -> CallToolResult {
+    if req.project.is_none() {
+        return CallToolResult::success(vec![Content::text(
+            "Error: 'project' filter is required for deps_tree action".to_string(),
+        )]);
     }
 
-    let mut file = fs::File::create(&cache_path)?;
-    for project in projects {
-        writeln!(file, "{}", project.display())?;
-    }
-    Ok(())
-}
+    let project = match projects.as_slice() {
+        [] => {
+            return CallToolResult::success(vec![Content::text(
+                "No matching projects found".to_string(),
+            )]);
+        }
+        [project] => project,
+        _ => {
+            return CallToolResult::success(vec![Content::text(format!(
+                "Error: deps_tree needs a single project, but {} matched. Use a more specific 'project' filter",
+                projects.len()
+            ))]);
+        }
+    };
 
-fn scan_elixir_projects(path: Option<&str>) -> Vec<PathBuf> {
-    let default_path = dirs::home_dir()
-        .map(|h| h.join("src/flt"))
-        .unwrap_or_else(|| PathBuf::from("."));
-
-    let start_path = path
-        .map(|p| {
-            if p.starts_with("~/") {
-                dirs::home_dir()
-                    .map(|h| h.join(&p[2..]))
-                    .unwrap_or_else(|| PathBuf::from(p))
-            } else {
-                PathBuf::from(p)
-            }
-        })
-        .unwrap_or(default_path);
+    let project_name = project
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| project.display().to_string());
 
-    if !start_path.exists() {
-        return Vec::new();
+    let mut args = vec!["deps.tree"];
+    if let Some(ref package) = req.package {
+        args.push(package);
     }
 
-    // Directories to skip (dependencies, build artifacts, etc.)
-    let skip_dirs: HashSet<&str> = [
-        "deps",
-        "_build",
-        ".elixir_ls",
-        "node_modules",
-        ".git",
-        "_checkouts",
-    ]
-    .into_iter()
-    .collect();
-
-    let mut projects: Vec<PathBuf> = Vec::new();
+    let output = Command::new("mix")
+        .args(&args)
+        .current_dir(project)
+        .cancellable_output()
+        .await;
 
-    for entry in WalkDir::new(&start_path)
-        .follow_links(true)
-        .into_iter()
-        .filter_entry(|e| {
-            // Skip certain directories
-            if e.file_type().is_dir()
-                && let Some(name) = e.file_name().to_str()
-            {
-                return !skip_dirs.contains(name);
-            }
-            true
-        })
-        .filter_map(|e| e.ok())
-    {
-        if entry.file_type().is_file()
-            && entry.file_name() == "mix.exs"
-            && let Some(parent) = entry.path().parent()
-        {
-            projects.push(parent.to_path_buf());
-        }
-    }
+    let text = match output {
+        Ok(o) if o.status.success() => format!(
+            "Dependency tree for {}:\n{}",
+            project_name,
+            String::from_utf8_lossy(&o.stdout).trim_end()
+        ),
+        Ok(o) => format!("✗ {} {}", project_name, first_error_line(&o)),
+        Err(e) => format!("✗ {} {}", project_name, e),
+    };
 
-    projects.sort();
-    projects
+    CallToolResult::success(vec![Content::text(text)])
 }
 
-fn get_elixir_projects(path: Option<&str>, force_refresh: bool) -> Vec<PathBuf> {
-    let ignored = load_ignored_projects();
+async fn handle_xref(projects: Vec<PathBuf>, req: &ElixirProjectsRequest) -> CallToolResult {
+    if projects.is_empty() {
+        return CallToolResult::success(vec![Content::text(
+            "No Elixir projects found".to_string(),
+        )]);
+    }
 
-    let filter_ignored = |projects: Vec<PathBuf>| -> Vec<PathBuf> {
-        projects
-            .into_iter()
-            .filter(|p| {
-                p.file_name()
-                    .map(|n| !ignored.contains(&n.to_string_lossy().to_string()))
-                    .unwrap_or(true)
-            })
-            .collect()
+    let mode = req.xref_mode.as_deref().unwrap_or("stats");
+    let args: Vec<&str> = match (mode, req.module.as_deref()) {
+        ("stats", _) => vec!["xref", "graph", "--format", "stats"],
+        ("callers", Some(module)) => vec!["xref", "callers", module],
+        ("calls", Some(source)) => vec!["xref", "graph", "--source", source],
+        ("callers" | "calls", None) => {
+            return CallToolResult::success(vec![Content::text(format!(
+                "Error: 'module' is required for xref {} mode",
+                mode
+            ))]);
+        }
+        _ => {
+            return CallToolResult::success(vec![Content::text(format!(
+                "Unknown xref_mode '{}'. Use: stats, callers, calls",
+                mode
+            ))]);
+        }
     };
 
-    // If custom path specified, always scan (don't use cache)
-    if path.is_some() {
-        return filter_ignored(scan_elixir_projects(path));
-    }
+    let mut results: Vec<String> = Vec::new();
+    for project in &projects {
+        if shutdown_token().is_cancelled() {
+            results.push("- stopped: shutting down".to_string());
+            break;
+        }
+        let output = Command::new("mix")
+            .args(&args)
+            .current_dir(project)
+            .cancellable_output()
+            .await;
 
-    // Try to load from cache unless force refresh
-    if !force_refresh && let Some(projects) = load_projects_from_cache() {
-        return filter_ignored(projects);
-    }
+        let project_name = project
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| project.display().to_string());
+
+        let text = match output {
+            Ok(o) if o.status.success() => format!(
+                "📊 {}:\n{}",
+                project_name,
+                String::from_utf8_lossy(&o.stdout).trim_end()
+            ),
+            Ok(o) => format!("✗ {} {}", project_name, first_error_line(&o)),
+            Err(e) => format!("✗ {} {}", project_name, e),
+        };
+        results.push(text);
+    }
+
+    CallToolResult::success(vec![Content::text(results.join("\n\n"))])
+}
+
+async fn handle_ecto_reset(projects: Vec<PathBuf>, req: &ElixirProjectsRequest) -> CallToolResult {
+    let projects = match single_project(projects, req, "ecto_reset") {
+        Ok(project) => vec![project],
+        Err(e) => return CallToolResult::success(vec![Content::text(e)]),
+    };
+
+    let mix_env = req.env.as_deref().unwrap_or("test");
+
+    let mut results: Vec<String> = Vec::new();
+    for project in &projects {
+        if shutdown_token().is_cancelled() {
+            results.push("- stopped: shutting down".to_string());
+            break;
+        }
+        let output = Command::new("mix")
+            .args(["ecto.reset"])
+            .env("MIX_ENV", mix_env)
+            .current_dir(project)
+            .cancellable_output()
+            .await;
+
+        let project_name = project
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| project.display().to_string());
+
+        let status = match output {
+            Ok(o) if o.status.success() => "✓ (reset)".to_string(),
+            Ok(o) => format!("✗ {}", first_error_line(&o)),
+            Err(e) => format!("✗ {}", e),
+        };
+        results.push(format!("{} {}", project_name, status));
+    }
+
+    CallToolResult::success(vec![Content::text(format!(
+        "Ecto reset (MIX_ENV={}):\n{}",
+        mix_env,
+        results.join("\n")
+    ))])
+}
+
+async fn handle_git_push_force_with_lease(
+    projects: Vec<PathBuf>,
+    req: &ElixirProjectsRequest,
+) -> CallToolResult {
+    let projects = match single_project(projects, req, "git_push_force_with_lease") {
+        Ok(project) => vec![project],
+        Err(e) => return CallToolResult::success(vec![Content::text(e)]),
+    };
+
+    let mut results: Vec<String> = Vec::new();
+    for project in &projects {
+        if shutdown_token().is_cancelled() {
+            results.push("- stopped: shutting down".to_string());
+            break;
+        }
+        let project_name = project
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| project.display().to_string());
+
+        let remote_ref = git_stdout(
+            project,
+            &["rev-parse", "--abbrev-ref", "--symbolic-full-name", "@{u}"],
+        )
+        .await
+        .unwrap_or_else(|| "upstream".to_string());
+
+        let output = Command::new("git")
+            .args(["push", "--force-with-lease"])
+            .current_dir(project)
+            .cancellable_output()
+            .await;
+
+        let status = match output {
+            Ok(o) if o.status.success() => {
+                let sha = git_stdout(project, &["log", "-1", "--format=%H"])
+                    .await
+                    .unwrap_or_else(|| "unknown".to_string());
+                format!("✓ (pushed to {} at {})", remote_ref, sha)
+            }
+            Ok(o) => format!("✗ rejected: {}", first_error_line(&o)),
+            Err(e) => format!("✗ {}", e),
+        };
+        results.push(format!("{} {}", project_name, status));
+    }
+
+    CallToolResult::success(vec![Content::text(format!(
+        "Git push --force-with-lease:\n{}",
+        results.join("\n")
+    ))])
+}
+
+async fn handle_git_commit(projects: Vec<PathBuf>, req: &ElixirProjectsRequest) -> CallToolResult {
+    let Some(ref message) = req.commit_message else {
+        return CallToolResult::success(vec![Content::text(
+            "Error: 'commit_message' is required for git_commit action".to_string(),
+        )]);
+    };
+
+    let projects = match single_project(projects, req, "git_commit") {
+        Ok(project) => vec![project],
+        Err(e) => return CallToolResult::success(vec![Content::text(e)]),
+    };
+
+    let mut results: Vec<String> = Vec::new();
+    for project in &projects {
+        if shutdown_token().is_cancelled() {
+            results.push("- stopped: shutting down".to_string());
+            break;
+        }
+        let project_name = project
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| project.display().to_string());
+
+        // The first porcelain column is the index status; anything but ' ' or '?' is staged
+        let has_staged = git_stdout(project, &["status", "--porcelain"])
+            .await
+            .map(|stdout| {
+                stdout
+                    .lines()
+                    .any(|line| !line.starts_with(' ') && !line.starts_with('?'))
+            })
+            .unwrap_or(false);
+
+        if !has_staged {
+            results.push(format!("{} - (nothing staged, skipped)", project_name));
+            continue;
+        }
+
+        let output = Command::new("git")
+            .args(["commit", "-m", message])
+            .current_dir(project)
+            .cancellable_output()
+            .await;
+
+        let status = match output {
+            Ok(o) if o.status.success() => {
+                let sha = git_stdout(project, &["log", "-1", "--format=%H"])
+                    .await
+                    .unwrap_or_else(|| "unknown".to_string());
+                format!("✓ (committed {})", sha)
+            }
+            Ok(o) => format!("✗ {}", first_error_line(&o)),
+            Err(e) => format!("✗ {}", e),
+        };
+        results.push(format!("{} {}", project_name, status));
+    }
+
+    CallToolResult::success(vec![Content::text(format!(
+        "Git commit:\n{}",
+        results.join("\n")
+    ))])
+}
+
+async fn handle_git_add(projects: Vec<PathBuf>, req: &ElixirProjectsRequest) -> CallToolResult {
+    let projects = match single_project(projects, req, "git_add") {
+        Ok(project) => vec![project],
+        Err(e) => return CallToolResult::success(vec![Content::text(e)]),
+    };
+
+    // "--" keeps paths such as "-f" from being read as options
+    let mut args = vec!["add", "--"];
+    match req.files {
+        Some(ref files) if !files.is_empty() => args.extend(files.iter().map(String::as_str)),
+        _ => args.push("."),
+    }
+
+    let mut results: Vec<String> = Vec::new();
+    for project in &projects {
+        if shutdown_token().is_cancelled() {
+            results.push("- stopped: shutting down".to_string());
+            break;
+        }
+        let project_name = project
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| project.display().to_string());
+
+        let output = Command::new("git")
+            .args(&args)
+            .current_dir(project)
+            .cancellable_output()
+            .await;
+
+        let status = match output {
+            Ok(o) if o.status.success() => {
+                // The last line of --stat is the "N files changed, ..." summary
+                let staged = git_stdout(project, &["diff", "--cached", "--stat"])
+                    .await
+                    .and_then(|stat| stat.lines().last().map(|l| l.trim().to_string()))
+                    .unwrap_or_default();
+                if staged.is_empty() {
+                    "✓ (nothing staged)".to_string()
+                } else {
+                    format!("✓ ({} staged)", staged)
+                }
+            }
+            Ok(o) => format!("✗ {}", first_error_line(&o)),
+            Err(e) => format!("✗ {}", e),
+        };
+        results.push(format!("{} {}", project_name, status));
+    }
+
+    CallToolResult::success(vec![Content::text(format!(
+        "Git add:\n{}",
+        results.join("\n")
+    ))])
+}
+
+async fn handle_test(projects: Vec<PathBuf>, req: &ElixirProjectsRequest) -> CallToolResult {
+    if req.project.is_none() {
+        return CallToolResult::success(vec![Content::text(
+            "Error: 'project' filter is required for test action".to_string(),
+        )]);
+    }
+
+    if projects.is_empty() {
+        return CallToolResult::success(vec![Content::text(
+            "No matching projects found".to_string(),
+        )]);
+    }
+
+    let mut args = vec!["test"];
+    if let Some(ref test_file) = req.test_file {
+        args.push(test_file);
+    }
+    if let Some(ref pattern) = req.test_pattern {
+        args.extend(["--only", pattern]);
+    }
+
+    let mut results: Vec<String> = Vec::new();
+    for project in &projects {
+        if shutdown_token().is_cancelled() {
+            results.push("- stopped: shutting down".to_string());
+            break;
+        }
+        let project_name = project
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| project.display().to_string());
+
+        let output = Command::new("mix")
+            .args(&args)
+            .current_dir(project)
+            .cancellable_output()
+            .await;
+
+        let text = match output {
+            Ok(o) => {
+                let stdout = String::from_utf8_lossy(&o.stdout);
+                // e.g. "12 tests, 0 failures"
+                let summary = stdout
+                    .lines()
+                    .rev()
+                    .find(|line| line.contains("failure"))
+                    .map(str::trim)
+                    .unwrap_or("no test summary found");
+                let mark = if o.status.success() { "✓" } else { "✗" };
+                format!(
+                    "{} {} ({})\n{}",
+                    mark,
+                    project_name,
+                    summary,
+                    stdout.trim_end()
+                )
+            }
+            Err(e) => format!("✗ {} {}", project_name, e),
+        };
+        results.push(text);
+    }
+
+    CallToolResult::success(vec![Content::text(results.join("\n\n"))])
+}
+
+fn handle_changelog_summary(projects: Vec<PathBuf>) -> CallToolResult {
+    if projects.is_empty() {
+        return CallToolResult::success(vec![Content::text(
+            "No Elixir projects found".to_string(),
+        )]);
+    }
+
+    let mut results: Vec<String> = Vec::new();
+    for project in &projects {
+        let project_name = project
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| project.display().to_string());
+
+        let changelog = ["CHANGELOG.md", "CHANGES.md", "HISTORY.md"]
+            .iter()
+            .find_map(|name| fs::read_to_string(project.join(name)).ok());
+
+        let text = match changelog {
+            Some(content) => {
+                let sections = recent_changelog_sections(&content, 2);
+                if sections.is_empty() {
+                    format!("📝 {}: no version headings found", project_name)
+                } else {
+                    format!("📝 {}:\n{}", project_name, sections)
+                }
+            }
+            None => format!("- {}: no changelog", project_name),
+        };
+        results.push(text);
+    }
+
+    CallToolResult::success(vec![Content::text(results.join("\n\n"))])
+}
+
+fn handle_version_bump(projects: Vec<PathBuf>, req: &ElixirProjectsRequest) -> CallToolResult {
+    let bump_type = req.bump_type.as_deref().unwrap_or("patch");
+    if !matches!(bump_type, "major" | "minor" | "patch") {
+        return CallToolResult::success(vec![Content::text(format!(
+            "Unknown bump_type '{}'. Use: major, minor, patch",
+            bump_type
+        ))]);
+    }
+
+    let projects = match single_project(projects, req, "version_bump") {
+        Ok(project) => vec![project],
+        Err(e) => return CallToolResult::success(vec![Content::text(e)]),
+    };
+
+    // Matches both `version: "1.2.3"` and `@version "1.2.3-rc.1"`
+    let version_re =
+        Regex::new(r#"(version:\s*|@version\s+)"([^"]+)""#).expect("valid version regex");
+
+    let mut results: Vec<String> = Vec::new();
+    for project in &projects {
+        let project_name = project
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| project.display().to_string());
+
+        let mix_path = project.join("mix.exs");
+        let content = match fs::read_to_string(&mix_path) {
+            Ok(content) => content,
+            Err(e) => {
+                results.push(format!("✗ {}: {}", project_name, e));
+                continue;
+            }
+        };
+
+        let Some(caps) = version_re.captures(&content) else {
+            results.push(format!("✗ {}: no version found in mix.exs", project_name));
+            continue;
+        };
+
+        let old_version = caps[2].to_string();
+        let new_version = match Version::parse(&old_version)
+            .map_err(|e| e.to_string())
+            .and_then(|version| bump_version(&version, bump_type))
+        {
+            Ok(version) => version.to_string(),
+            Err(e) => {
+                results.push(format!(
+                    "✗ {}: invalid version '{}': {}",
+                    project_name, old_version, e
+                ));
+                continue;
+            }
+        };
+
+        let new_content = version_re.replace(&content, |caps: &regex::Captures| {
+            format!("{}\"{}\"", &caps[1], new_version)
+        });
+
+        match fs::write(&mix_path, new_content.as_bytes()) {
+            Ok(_) => results.push(format!(
+                "✓ {}: {} -> {}",
+                project_name, old_version, new_version
+            )),
+            Err(e) => results.push(format!("✗ {}: {}", project_name, e)),
+        }
+    }
+
+    CallToolResult::success(vec![Content::text(results.join("\n"))])
+}
+
+async fn handle_audit(projects: Vec<PathBuf>) -> CallToolResult {
+    if projects.is_empty() {
+        return CallToolResult::success(vec![Content::text(
+            "No Elixir projects found".to_string(),
+        )]);
+    }
+
+    let mut results: Vec<String> = Vec::new();
+    let mut clean_projects: Vec<String> = Vec::new();
+
+    for project in &projects {
+        if shutdown_token().is_cancelled() {
+            results.push("- stopped: shutting down".to_string());
+            break;
+        }
+        let project_name = project
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| project.display().to_string());
+
+        // (severity rank, description) - lower rank is more severe
+        let mut findings: Vec<(u8, String)> = Vec::new();
+
+        match Command::new("mix")
+            .args(["hex.audit"])
+            .current_dir(project)
+            .cancellable_output()
+            .await
+        {
+            Ok(o) => findings.extend(parse_hex_audit(&String::from_utf8_lossy(&o.stdout))),
+            Err(e) => findings.push((0, format!("hex.audit failed: {}", e))),
+        }
+
+        let uses_sobelow = fs::read_to_string(project.join("mix.exs"))
+            .map(|content| content.contains(":sobelow"))
+            .unwrap_or(false);
+
+        if uses_sobelow {
+            match Command::new("mix")
+                .args(["sobelow", "--config", "--format", "json"])
+                .current_dir(project)
+                .cancellable_output()
+                .await
+            {
+                Ok(o) => findings.extend(parse_sobelow(&String::from_utf8_lossy(&o.stdout))),
+                Err(e) => findings.push((0, format!("sobelow failed: {}", e))),
+            }
+        }
+
+        if findings.is_empty() {
+            clean_projects.push(project_name);
+            continue;
+        }
+
+        findings.sort();
+        let labels = ["HIGH", "MEDIUM", "LOW"];
+        let lines: Vec<String> = findings
+            .iter()
+            .map(|(rank, text)| format!("[{}] {}", labels[*rank as usize], text))
+            .collect();
+        results.push(format!(
+            "🔒 {} ({} findings):\n  {}",
+            project_name,
+            findings.len(),
+            lines.join("\n  ")
+        ));
+    }
+
+    if !clean_projects.is_empty() {
+        results.push(format!(
+            "✅ Clean ({}): {}",
+            clean_projects.len(),
+            clean_projects.join(", ")
+        ));
+    }
+
+    CallToolResult::success(vec![Content::text(results.join("\n\n"))])
+}
+
+async fn handle_coverage(projects: Vec<PathBuf>, req: &ElixirProjectsRequest) -> CallToolResult {
+    if projects.is_empty() {
+        return CallToolResult::success(vec![Content::text(
+            "No Elixir projects found".to_string(),
+        )]);
+    }
+
+    let min_coverage = req.min_coverage.unwrap_or(70.0);
+    let mut covered: Vec<(f64, String)> = Vec::new();
+    let mut failed: Vec<String> = Vec::new();
+
+    for project in &projects {
+        if shutdown_token().is_cancelled() {
+            break;
+        }
+        let project_name = project
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| project.display().to_string());
+
+        let output = Command::new("mix")
+            .args(["coveralls"])
+            .env("MIX_ENV", "test")
+            .current_dir(project)
+            .cancellable_output()
+            .await;
+
+        match output {
+            Ok(o) => match parse_coverage_total(&String::from_utf8_lossy(&o.stdout)) {
+                Some(percent) => covered.push((percent, project_name)),
+                None => failed.push(format!("✗ {} {}", project_name, first_error_line(&o))),
+            },
+            Err(e) => failed.push(format!("✗ {} {}", project_name, e)),
+        }
+    }
+
+    covered.sort_by(|a, b| a.0.total_cmp(&b.0));
+    let below = covered.iter().filter(|(p, _)| *p < min_coverage).count();
+
+    let mut lines: Vec<String> = covered
+        .iter()
+        .map(|(percent, name)| {
+            let mark = if *percent < min_coverage {
+                "⚠️ "
+            } else {
+                "✓"
+            };
+            format!("{} {:>6.1}% {}", mark, percent, name)
+        })
+        .collect();
+    lines.extend(failed);
+
+    CallToolResult::success(vec![Content::text(format!(
+        "Coverage for {} projects ({} below {:.0}%):\n{}",
+        projects.len(),
+        below,
+        min_coverage,
+        lines.join("\n")
+    ))])
+}
+
+fn handle_import(req: &ElixirProjectsRequest) -> CallToolResult {
+    let Some(ref file) = req.file else {
+        return CallToolResult::success(vec![Content::text(
+            "Error: 'file' is required for import action".to_string(),
+        )]);
+    };
+
+    let file_path = expand_path(file);
+    let content = match fs::read_to_string(&file_path) {
+        Ok(content) => content,
+        Err(e) => {
+            return CallToolResult::success(vec![Content::text(format!(
+                "✗ Failed to read {}: {}",
+                file_path.display(),
+                e
+            ))]);
+        }
+    };
+
+    // Imports are kept in their own list, which every scan of the default
+    // root merges in, so a refresh does not drop them
+    let mut imported = load_imported_projects();
+    let mut added = 0;
+    let mut skipped = 0;
+    let mut invalid: Vec<String> = Vec::new();
+
+    for line in content.lines().map(str::trim).filter(|l| !l.is_empty()) {
+        let path = expand_path(line);
+        if !path.join("mix.exs").exists() {
+            invalid.push(line.to_string());
+        } else if imported.contains(&path) {
+            skipped += 1;
+        } else {
+            imported.push(path);
+            added += 1;
+        }
+    }
+
+    if added > 0 {
+        imported.sort();
+        if let Err(e) = write_project_list(&get_imported_path(), None, &imported) {
+            return CallToolResult::success(vec![Content::text(format!(
+                "✗ Failed to save imported projects: {}",
+                e
+            ))]);
+        }
+        // Without a cache, the next lookup scans and merges the imports itself
+        if let Some(cached) = load_projects_from_cache() {
+            let _ = save_projects_to_cache(&merge_imported(cached));
+        }
+    }
+
+    let mut output = format!(
+        "Imported from {}: {} added, {} already present, {} invalid",
+        file_path.display(),
+        added,
+        skipped,
+        invalid.len()
+    );
+    if !invalid.is_empty() {
+        output.push_str(&format!("\nInvalid paths:\n  {}", invalid.join("\n  ")));
+    }
+
+    CallToolResult::success(vec![Content::text(output)])
+}
+
+fn handle_export(projects: Vec<PathBuf>, req: &ElixirProjectsRequest) -> CallToolResult {
+    let output_path = req
+        .output_file
+        .as_deref()
+        .map(expand_path)
+        .unwrap_or_else(get_export_path);
+
+    let output = match write_project_list(&output_path, None, &projects) {
+        Ok(_) => format!(
+            "Exported {} projects to {}",
+            projects.len(),
+            output_path.display()
+        ),
+        Err(e) => format!("✗ Failed to write {}: {}", output_path.display(), e),
+    };
+    CallToolResult::success(vec![Content::text(output)])
+}
+
+async fn handle_load_test(projects: Vec<PathBuf>, req: &ElixirProjectsRequest) -> CallToolResult {
+    if req.project.is_none() {
+        return CallToolResult::success(vec![Content::text(
+            "Error: 'project' filter is required for load_test action".to_string(),
+        )]);
+    }
+
+    if projects.is_empty() {
+        return CallToolResult::success(vec![Content::text(
+            "No matching projects found".to_string(),
+        )]);
+    }
+
+    let url = req.url.as_deref().unwrap_or("http://localhost:4000/");
+    let requests = req.requests.unwrap_or(200).to_string();
+    let concurrency = req.concurrency.unwrap_or(10).to_string();
+    let timeout = Duration::from_secs(load_config().command_timeout_secs);
+
+    let mut results: Vec<String> = Vec::new();
+    for project in &projects {
+        if shutdown_token().is_cancelled() {
+            results.push("- stopped: shutting down".to_string());
+            break;
+        }
+        let project_name = project
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| project.display().to_string());
+
+        // Prefer a project-defined `mix loadtest` task, otherwise fall back to `hey`
+        let has_mix_task = matches!(
+            run_with_timeout(
+                Command::new("mix").args(["help", "loadtest"]).current_dir(project),
+                timeout,
+            )
+            .await,
+            Ok(ref o) if o.status.success()
+        );
+
+        let text = if has_mix_task {
+            match Command::new("mix")
+                .args(["loadtest", "--url", url])
+                .args(["--requests", &requests, "--concurrency", &concurrency])
+                .current_dir(project)
+                .cancellable_output()
+                .await
+            {
+                Ok(o) if o.status.success() => {
+                    let stdout = String::from_utf8_lossy(&o.stdout);
+                    match summarize_loadtest_output(&stdout, &requests) {
+                        Some(summary) => format!(
+                            "✓ {} (mix loadtest, {} requests, {} concurrent against {}):\n{}",
+                            project_name, requests, concurrency, url, summary
+                        ),
+                        None => format!(
+                            "✓ {} (mix loadtest, no summary found in output):\n{}",
+                            project_name,
+                            verbose_details(&o)
+                        ),
+                    }
+                }
+                Ok(o) => format!("✗ {} {}", project_name, first_error_line(&o)),
+                Err(e) => format!("✗ {} {}", project_name, e),
+            }
+        } else {
+            match Command::new("hey")
+                .args(["-n", &requests, "-c", &concurrency, url])
+                .current_dir(project)
+                .cancellable_output()
+                .await
+            {
+                Ok(o) if o.status.success() => format!(
+                    "✓ {} ({} requests, {} concurrent against {}):\n{}",
+                    project_name,
+                    requests,
+                    concurrency,
+                    url,
+                    summarize_hey_output(&String::from_utf8_lossy(&o.stdout), &requests)
+                ),
+                Ok(o) => format!("✗ {} {}", project_name, first_error_line(&o)),
+                Err(e) => format!("✗ {} failed to run hey: {}", project_name, e),
+            }
+        };
+        results.push(text);
+    }
+
+    CallToolResult::success(vec![Content::text(results.join("\n\n"))])
+}
+
+async fn handle_health_check(projects: Vec<PathBuf>) -> CallToolResult {
+    if projects.is_empty() {
+        return CallToolResult::success(vec![Content::text(
+            "No Elixir projects found".to_string(),
+        )]);
+    }
+
+    let client = match reqwest::Client::builder()
+        .timeout(Duration::from_secs(5))
+        .build()
+    {
+        Ok(client) => client,
+        Err(e) => {
+            return CallToolResult::success(vec![Content::text(format!(
+                "✗ Failed to create HTTP client: {}",
+                e
+            ))]);
+        }
+    };
+
+    let mut results: Vec<String> = Vec::new();
+    for project in &projects {
+        if shutdown_token().is_cancelled() {
+            results.push("- stopped: shutting down".to_string());
+            break;
+        }
+        let project_name = project
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| project.display().to_string());
+
+        let Some(port) = dev_http_port(project) else {
+            results.push(format!(
+                "- {} (no http port in config/dev.exs)",
+                project_name
+            ));
+            continue;
+        };
+
+        let url = format!("http://localhost:{}/", port);
+        let started = Instant::now();
+        let text = match client.get(&url).send().await {
+            Ok(response) => {
+                let status = response.status();
+                let elapsed = started.elapsed();
+                let body = response.text().await.unwrap_or_default();
+                let snippet: String = body.chars().take(200).collect();
+                format!(
+                    "{} {} {} ({} ms)\n  {}",
+                    if status.is_server_error() {
+                        "✗"
+                    } else {
+                        "✓"
+                    },
+                    project_name,
+                    status,
+                    elapsed.as_millis(),
+                    snippet.trim().replace('\n', "\n  ")
+                )
+            }
+            Err(e) => format!("✗ {} not reachable at {}: {}", project_name, url, e),
+        };
+        results.push(text);
+    }
+
+    CallToolResult::success(vec![Content::text(results.join("\n"))])
+}
+
+async fn handle_docker_build(
+    projects: Vec<PathBuf>,
+    req: &ElixirProjectsRequest,
+) -> CallToolResult {
+    if projects.is_empty() {
+        return CallToolResult::success(vec![Content::text(
+            "No Elixir projects found".to_string(),
+        )]);
+    }
+
+    let mut results: Vec<String> = Vec::new();
+    for project in &projects {
+        if shutdown_token().is_cancelled() {
+            results.push("- stopped: shutting down".to_string());
+            break;
+        }
+        let project_name = project
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| project.display().to_string());
+
+        if !project.join("Dockerfile").exists() {
+            results.push(format!("- {} (no Dockerfile)", project_name));
+            continue;
+        }
+
+        let tag = req
+            .docker_tag
+            .clone()
+            .unwrap_or_else(|| format!("{}:latest", project_name.to_lowercase()));
+
+        let mut args = vec!["build", "-t", tag.as_str()];
+        if req.no_cache.unwrap_or(false) {
+            args.push("--no-cache");
+        }
+        args.push(".");
+
+        let started = Instant::now();
+        let output = Command::new("docker")
+            .args(&args)
+            .current_dir(project)
+            .cancellable_output()
+            .await;
+        let elapsed = started.elapsed();
+
+        let status = match output {
+            Ok(o) if o.status.success() => {
+                let image_id = Command::new("docker")
+                    .args(["image", "inspect", "--format", "{{.Id}}", &tag])
+                    .cancellable_output()
+                    .await
+                    .ok()
+                    .filter(|o| o.status.success())
+                    .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+                    .unwrap_or_else(|| "unknown".to_string());
+                format!(
+                    "✓ {} built in {:.1}s ({})",
+                    tag,
+                    elapsed.as_secs_f64(),
+                    image_id
+                )
+            }
+            Ok(o) => {
+                let stderr = String::from_utf8_lossy(&o.stderr);
+                // BuildKit reports the failing step near the end of its output
+                let error = stderr
+                    .lines()
+                    .rev()
+                    .map(str::trim)
+                    .find(|line| line.starts_with("ERROR") || line.starts_with("error"))
+                    .unwrap_or("failed");
+                format!("✗ {}", error)
+            }
+            Err(e) => format!("✗ {}", e),
+        };
+        results.push(format!("{} {}", project_name, status));
+    }
+
+    CallToolResult::success(vec![Content::text(format!(
+        "Docker build on {} projects:\n{}",
+        projects.len(),
+        results.join("\n")
+    ))])
+}
+
+async fn handle_mise_check(projects: Vec<PathBuf>) -> CallToolResult {
+    if projects.is_empty() {
+        return CallToolResult::success(vec![Content::text(
+            "No Elixir projects found".to_string(),
+        )]);
+    }
+
+    let installed = match mise_installed_versions().await {
+        Ok(installed) => installed,
+        Err(e) => return CallToolResult::success(vec![Content::text(e)]),
+    };
+
+    let mut results: Vec<String> = Vec::new();
+    for project in &projects {
+        let project_name = project
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| project.display().to_string());
+
+        let required = mise_required_versions(project);
+        if required.is_empty() {
+            results.push(format!(
+                "- {} (no .mise.toml or .tool-versions)",
+                project_name
+            ));
+            continue;
+        }
+
+        // mise accepts prefixes like "1.16" and "latest" for any installed version
+        let missing: Vec<String> = required
+            .iter()
+            .filter(|(tool, version)| {
+                !installed.get(tool).is_some_and(|versions| {
+                    versions.iter().any(|v| {
+                        version == "latest"
+                            || v == version
+                            || v.starts_with(&format!("{}.", version))
+                    })
+                })
+            })
+            .map(|(tool, version)| format!("{} {}", tool, version))
+            .collect();
+
+        if missing.is_empty() {
+            results.push(format!("✓ {}", project_name));
+        } else {
+            results.push(format!(
+                "✗ {} missing: {}",
+                project_name,
+                missing.join(", ")
+            ));
+        }
+    }
+
+    CallToolResult::success(vec![Content::text(results.join("\n"))])
+}
+
+async fn handle_nix_info(projects: Vec<PathBuf>) -> CallToolResult {
+    if projects.is_empty() {
+        return CallToolResult::success(vec![Content::text(
+            "No Elixir projects found".to_string(),
+        )]);
+    }
+
+    let timeout = Duration::from_secs(load_config().command_timeout_secs);
+    let mut results: Vec<String> = Vec::new();
+    for project in &projects {
+        if shutdown_token().is_cancelled() {
+            results.push("- stopped: shutting down".to_string());
+            break;
+        }
+        let project_name = project
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| project.display().to_string());
+
+        if !project.join("flake.nix").exists() {
+            results.push(format!("- {} (no flake.nix)", project_name));
+            continue;
+        }
+
+        let output = run_with_timeout(
+            Command::new("nix")
+                .args(["flake", "show", "--json"])
+                .current_dir(project),
+            timeout,
+        )
+        .await;
+
+        let text = match output {
+            Ok(o) if o.status.success() => {
+                match serde_json::from_slice::<serde_json::Value>(&o.stdout) {
+                    Ok(flake) => {
+                        format!(
+                            "✓ {}\n  devShells: {}\n  packages: {}",
+                            project_name,
+                            flake_outputs(&flake, "devShells"),
+                            flake_outputs(&flake, "packages")
+                        )
+                    }
+                    Err(e) => format!("✗ {} invalid flake output: {}", project_name, e),
+                }
+            }
+            Ok(o) => format!("✗ {} {}", project_name, first_error_line(&o)),
+            Err(e) => format!("✗ {} {}", project_name, e),
+        };
+        results.push(text);
+    }
+
+    CallToolResult::success(vec![Content::text(results.join("\n"))])
+}
+
+async fn handle_nix_build(projects: Vec<PathBuf>) -> CallToolResult {
+    if projects.is_empty() {
+        return CallToolResult::success(vec![Content::text(
+            "No Elixir projects found".to_string(),
+        )]);
+    }
+
+    let timeout = Duration::from_secs(load_config().command_timeout_secs);
+    let mut results: Vec<String> = Vec::new();
+    for project in &projects {
+        if shutdown_token().is_cancelled() {
+            results.push("- stopped: shutting down".to_string());
+            break;
+        }
+        let project_name = project
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| project.display().to_string());
+
+        if !project.join("flake.nix").exists() {
+            results.push(format!("- {} (no flake.nix)", project_name));
+            continue;
+        }
+
+        let started = Instant::now();
+        let output = run_with_timeout(
+            Command::new("nix")
+                .args(["build", "--print-out-paths"])
+                .current_dir(project),
+            timeout,
+        )
+        .await;
+
+        let text = match output {
+            Ok(o) if o.status.success() => format!(
+                "✓ {} built in {:.1}s\n  {}",
+                project_name,
+                started.elapsed().as_secs_f64(),
+                String::from_utf8_lossy(&o.stdout)
+                    .trim()
+                    .replace('\n', "\n  ")
+            ),
+            Ok(o) => format!("✗ {} {}", project_name, first_error_line(&o)),
+            Err(e) => format!("✗ {} {}", project_name, e),
+        };
+        results.push(text);
+    }
+
+    CallToolResult::success(vec![Content::text(results.join("\n"))])
+}
+
+async fn handle_deps_get(projects: Vec<PathBuf>) -> CallToolResult {
+    if projects.is_empty() {
+        return CallToolResult::success(vec![Content::text(
+            "No Elixir projects found".to_string(),
+        )]);
+    }
+
+    let mut results: Vec<String> = Vec::new();
+    for project in &projects {
+        if shutdown_token().is_cancelled() {
+            results.push("- stopped: shutting down".to_string());
+            break;
+        }
+        let output = Command::new("mix")
+            .arg("deps.get")
+            .current_dir(project)
+            .cancellable_output()
+            .await;
+
+        let project_name = project
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| project.display().to_string());
+
+        let status = match output {
+            Ok(o) if o.status.success() => {
+                // Each downloaded dep is reported as "* Getting plug (Hex package)"
+                let stdout = String::from_utf8_lossy(&o.stdout);
+                let fetched = stdout
+                    .lines()
+                    .filter(|line| line.trim_start().starts_with("* Getting"))
+                    .count();
+                if fetched == 0 {
+                    "✓ (already fetched)".to_string()
+                } else {
+                    format!("✓ ({} deps fetched)", fetched)
+                }
+            }
+            Ok(o) => {
+                let combined = format!(
+                    "{}\n{}",
+                    String::from_utf8_lossy(&o.stdout),
+                    String::from_utf8_lossy(&o.stderr)
+                );
+                let unreachable = ["nxdomain", "econnrefused", "timeout", "Failed to fetch"]
+                    .iter()
+                    .any(|marker| combined.contains(marker));
+                if unreachable {
+                    format!("✗ Hex.pm unreachable: {}", first_error_line(&o))
+                } else {
+                    format!("✗ {}", first_error_line(&o))
+                }
+            }
+            Err(e) => format!("✗ {}", e),
+        };
+        results.push(format!("{} {}", project_name, status));
+    }
+
+    CallToolResult::success(vec![Content::text(format!(
+        "mix deps.get on {} projects:\n{}",
+        projects.len(),
+        results.join("\n")
+    ))])
+}
+
+async fn handle_compile_warnings(projects: Vec<PathBuf>) -> CallToolResult {
+    if projects.is_empty() {
+        return CallToolResult::success(vec![Content::text(
+            "No Elixir projects found".to_string(),
+        )]);
+    }
+
+    let timeout = Duration::from_secs(load_config().command_timeout_secs);
+    let mut by_category: HashMap<&'static str, usize> = HashMap::new();
+    let mut by_project: Vec<(usize, String)> = Vec::new();
+    let mut failed: Vec<String> = Vec::new();
+
+    for project in &projects {
+        if shutdown_token().is_cancelled() {
+            failed.push("- stopped: shutting down".to_string());
+            break;
+        }
+        let project_name = project
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| project.display().to_string());
+
+        let output = run_with_timeout(
+            Command::new("mix")
+                .args(["compile", "--force", "--warnings-as-errors"])
+                .current_dir(project),
+            timeout,
+        )
+        .await;
+
+        let o = match output {
+            Ok(o) => o,
+            Err(e) => {
+                failed.push(format!("✗ {} {}", project_name, e));
+                continue;
+            }
+        };
+        let stderr = String::from_utf8_lossy(&o.stderr);
+        let warnings: Vec<&str> = stderr
+            .lines()
+            .filter_map(|line| line.trim_start().strip_prefix("warning:"))
+            .collect();
+
+        // --warnings-as-errors fails the build for warnings alone; anything
+        // else is a real compile error
+        if !o.status.success() && warnings.is_empty() {
+            failed.push(format!("✗ {} {}", project_name, first_error_line(&o)));
+            continue;
+        }
+        for warning in &warnings {
+            *by_category.entry(warning_category(warning)).or_insert(0) += 1;
+        }
+        by_project.push((warnings.len(), project_name));
+    }
+
+    let total: usize = by_project.iter().map(|(count, _)| count).sum();
+    let mut categories: Vec<(&str, usize)> = by_category.into_iter().collect();
+    categories.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+    by_project.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+
+    let mut sections = vec![format!(
+        "{} warnings across {} projects ({} clean)",
+        total,
+        by_project.len(),
+        by_project.iter().filter(|(count, _)| *count == 0).count()
+    )];
+    if !categories.is_empty() {
+        let lines: Vec<String> = categories
+            .iter()
+            .map(|(category, count)| format!("  {:>4}  {}", count, category))
+            .collect();
+        sections.push(format!("By category:\n{}", lines.join("\n")));
+        let lines: Vec<String> = by_project
+            .iter()
+            .filter(|(count, _)| *count > 0)
+            .take(10)
+            .map(|(count, name)| format!("  {:>4}  {}", count, name))
+            .collect();
+        sections.push(format!("Top projects:\n{}", lines.join("\n")));
+    }
+    if !failed.is_empty() {
+        sections.push(format!("Failed to compile:\n{}", failed.join("\n")));
+    }
+
+    CallToolResult::success(vec![Content::text(sections.join("\n\n"))])
+}
+
+async fn handle_stale(projects: Vec<PathBuf>, req: &ElixirProjectsRequest) -> CallToolResult {
+    if projects.is_empty() {
+        return CallToolResult::success(vec![Content::text(
+            "No Elixir projects found".to_string(),
+        )]);
+    }
+
+    let days = req.days.unwrap_or(90);
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+
+    let mut stale: Vec<(u64, String)> = Vec::new();
+    let mut no_commits: Vec<String> = Vec::new();
+    for project in &projects {
+        if shutdown_token().is_cancelled() {
+            break;
+        }
+        let project_name = project
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| project.display().to_string());
+
+        match git_stdout(project, &["log", "-1", "--format=%ct"])
+            .await
+            .and_then(|ts| ts.parse::<u64>().ok())
+        {
+            Some(committed) if now.saturating_sub(committed) / 86_400 >= days => {
+                stale.push((committed, project_name));
+            }
+            Some(_) => {}
+            None => no_commits.push(format!("- {} (no commits)", project_name)),
+        }
+    }
+
+    // Oldest last commit first
+    stale.sort();
+    let mut lines: Vec<String> = stale
+        .iter()
+        .map(|(committed, name)| {
+            let date = chrono::DateTime::from_timestamp(*committed as i64, 0)
+                .map(|d| d.format("%Y-%m-%d").to_string())
+                .unwrap_or_default();
+            format!(
+                "{} - last commit {} ({} days ago)",
+                name,
+                date,
+                now.saturating_sub(*committed) / 86_400
+            )
+        })
+        .collect();
+    lines.extend(no_commits);
+
+    let text = if lines.is_empty() {
+        format!(
+            "No stale projects: all {} have commits in the last {} days",
+            projects.len(),
+            days
+        )
+    } else {
+        format!(
+            "{} of {} projects have no commits in the last {} days:\n{}",
+            stale.len(),
+            projects.len(),
+            days,
+            lines.join("\n")
+        )
+    };
+
+    CallToolResult::success(vec![Content::text(text)])
+}
+
+async fn handle_git_remote(projects: Vec<PathBuf>, req: &ElixirProjectsRequest) -> CallToolResult {
+    if projects.is_empty() {
+        return CallToolResult::success(vec![Content::text(
+            "No Elixir projects found".to_string(),
+        )]);
+    }
+
+    let add = match req.remote_action.as_deref().unwrap_or("list") {
+        "list" => None,
+        "add" => match (req.remote_name.as_deref(), req.remote_url.as_deref()) {
+            (Some(name), Some(url)) => Some((name, url)),
+            _ => {
+                return CallToolResult::success(vec![Content::text(
+                    "Error: 'remote_name' and 'remote_url' are required to add a remote"
+                        .to_string(),
+                )]);
+            }
+        },
+        other => {
+            return CallToolResult::success(vec![Content::text(format!(
+                "Unknown remote_action '{}'. Use: list, add",
+                other
+            ))]);
+        }
+    };
+
+    let mut results: Vec<String> = Vec::new();
+    for project in &projects {
+        if shutdown_token().is_cancelled() {
+            results.push("- stopped: shutting down".to_string());
+            break;
+        }
+        let project_name = project
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| project.display().to_string());
+
+        if let Some((name, url)) = add {
+            let output = Command::new("git")
+                .args(["remote", "add", name, url])
+                .current_dir(project)
+                .cancellable_output()
+                .await;
+            results.push(match output {
+                Ok(o) if o.status.success() => format!("✓ {} added {} {}", project_name, name, url),
+                Ok(o) => format!("✗ {} {}", project_name, first_error_line(&o)),
+                Err(e) => format!("✗ {} {}", project_name, e),
+            });
+            continue;
+        }
+
+        let Some(remotes) = git_stdout(project, &["remote", "-v"]).await else {
+            results.push(format!("- {} (not a git repository)", project_name));
+            continue;
+        };
+        if remotes.is_empty() {
+            results.push(format!("- {} (no remotes)", project_name));
+            continue;
+        }
+
+        // "origin\tgit@github.com:me/app.git (fetch)"
+        let mut table: Vec<(String, String, String)> = Vec::new();
+        for line in remotes.lines() {
+            let mut parts = line.split_whitespace();
+            let (Some(name), Some(url), Some(kind)) = (parts.next(), parts.next(), parts.next())
+            else {
+                continue;
+            };
+            let index = match table.iter().position(|(n, _, _)| n == name) {
+                Some(index) => index,
+                None => {
+                    table.push((name.to_string(), String::new(), String::new()));
+                    table.len() - 1
+                }
+            };
+            if kind == "(push)" {
+                table[index].2 = url.to_string();
+            } else {
+                table[index].1 = url.to_string();
+            }
+        }
+
+        let width = table.iter().map(|(n, _, _)| n.len()).max().unwrap_or(0);
+        let rows: Vec<String> = table
+            .iter()
+            .map(|(name, fetch, push)| {
+                if fetch == push {
+                    format!("  {:<width$}  {}", name, fetch)
+                } else {
+                    format!("  {:<width$}  {} (fetch)  {} (push)", name, fetch, push)
+                }
+            })
+            .collect();
+        results.push(format!("{}\n{}", project_name, rows.join("\n")));
+    }
+
+    CallToolResult::success(vec![Content::text(results.join("\n"))])
+}
+
+async fn handle_summary(projects: Vec<PathBuf>) -> CallToolResult {
+    let project = match projects.as_slice() {
+        [project] => project,
+        [] => {
+            return CallToolResult::success(vec![Content::text(
+                "No matching projects found".to_string(),
+            )]);
+        }
+        _ => {
+            return CallToolResult::success(vec![Content::text(format!(
+                "Error: summary needs exactly one project but {} match; narrow it with 'project'",
+                projects.len()
+            ))]);
+        }
+    };
+
+    let mix_exs = fs::read_to_string(project.join("mix.exs")).unwrap_or_default();
+    let app_re = Regex::new(r"app:\s*:(\w+)").expect("valid app regex");
+    let version_re =
+        Regex::new(r#"(?:version:\s*|@version\s+)"([^"]+)""#).expect("valid version regex");
+    let app = app_re
+        .captures(&mix_exs)
+        .map(|c| c[1].to_string())
+        .or_else(|| project.file_name().map(|n| n.to_string_lossy().to_string()))
+        .unwrap_or_default();
+    let version = version_re
+        .captures(&mix_exs)
+        .map(|c| c[1].to_string())
+        .unwrap_or_else(|| "unknown".to_string());
+
+    let branch = git_stdout(project, &["rev-parse", "--abbrev-ref", "HEAD"])
+        .await
+        .unwrap_or_else(|| "(not a git repository)".to_string());
+    let last_commit = git_stdout(project, &["log", "-1", "--format=%s (%cr, %ci)"])
+        .await
+        .unwrap_or_else(|| "(none)".to_string());
+
+    let timeout = Duration::from_secs(load_config().command_timeout_secs);
+    // hex.outdated exits 1 when anything is outdated, so read stdout regardless
+    let outdated = match run_with_timeout(
+        Command::new("mix")
+            .args(["hex.outdated", "--all"])
+            .current_dir(project),
+        timeout,
+    )
+    .await
+    {
+        Ok(o) if !o.stdout.is_empty() => {
+            let count = String::from_utf8_lossy(&o.stdout)
+                .lines()
+                .filter(|line| {
+                    line.contains("Update possible") || line.contains("Update not possible")
+                })
+                .count();
+            count.to_string()
+        }
+        Ok(o) => format!("✗ {}", first_error_line(&o)),
+        Err(e) => format!("✗ {}", e),
+    };
+
+    let tests = match run_with_timeout(
+        Command::new("mix")
+            .args(["test", "--no-start"])
+            .current_dir(project),
+        timeout,
+    )
+    .await
+    {
+        Ok(o) => {
+            // "42 tests, 0 failures" or "1 doctest, 3 tests, 1 failure"
+            let stdout = String::from_utf8_lossy(&o.stdout);
+            let totals = stdout
+                .lines()
+                .find(|line| line.contains(" failure"))
+                .map(str::trim)
+                .unwrap_or("");
+            match (o.status.success(), totals.is_empty()) {
+                (true, true) => "✓ passing".to_string(),
+                (true, false) => format!("✓ passing ({})", totals),
+                (false, false) => format!("✗ failing ({})", totals),
+                (false, true) => format!("✗ {}", first_error_line(&o)),
+            }
+        }
+        Err(e) => format!("✗ {}", e),
+    };
+
+    let text = format!(
+        "# {} {}\n\n`{}`\n\n- **Branch:** {}\n- **Last commit:** {}\n- **Outdated deps:** {}\n- **Tests:** {}",
+        app,
+        version,
+        project.display(),
+        branch,
+        last_commit,
+        outdated,
+        tests
+    );
+    CallToolResult::success(vec![Content::text(text)])
+}
+
+fn handle_dep_graph(projects: Vec<PathBuf>, req: &ElixirProjectsRequest) -> CallToolResult {
+    if projects.is_empty() {
+        return CallToolResult::success(vec![Content::text(
+            "No Elixir projects found".to_string(),
+        )]);
+    }
+    let dot = match req.graph_format.as_deref().unwrap_or("tree") {
+        "tree" => false,
+        "dot" => true,
+        other => {
+            return CallToolResult::success(vec![Content::text(format!(
+                "Unknown graph_format '{}'. Use: tree, dot",
+                other
+            ))]);
+        }
+    };
+
+    let graph = project_dep_graph(&projects);
+    let edge_count: usize = graph.values().map(Vec::len).sum();
+    if edge_count == 0 {
+        return CallToolResult::success(vec![Content::text(format!(
+            "None of the {} projects depend on each other through path or git deps",
+            projects.len()
+        ))]);
+    }
+
+    // Kahn's algorithm: a project is ready once all of its deps are ordered
+    let mut remaining: HashMap<&str, usize> = graph
+        .iter()
+        .map(|(name, deps)| (name.as_str(), deps.len()))
+        .collect();
+    let mut order: Vec<&str> = Vec::new();
+    loop {
+        let mut ready: Vec<&str> = remaining
+            .iter()
+            .filter(|(_, pending)| **pending == 0)
+            .map(|(name, _)| *name)
+            .collect();
+        if ready.is_empty() {
+            break;
+        }
+        ready.sort();
+        for name in ready {
+            remaining.remove(name);
+            order.push(name);
+            for (dependent, deps) in &graph {
+                if deps.iter().any(|d| d == name)
+                    && let Some(pending) = remaining.get_mut(dependent.as_str())
+                {
+                    *pending -= 1;
+                }
+            }
+        }
+    }
+
+    let mut sections: Vec<String> = Vec::new();
+    if dot {
+        let mut names: Vec<&String> = graph.keys().collect();
+        names.sort();
+        let mut lines = vec!["digraph deps {".to_string()];
+        for name in names {
+            for dep in &graph[name] {
+                lines.push(format!("  \"{}\" -> \"{}\";", name, dep));
+            }
+        }
+        lines.push("}".to_string());
+        sections.push(lines.join("\n"));
+    } else {
+        // Roots are projects nothing else depends on
+        let mut roots: Vec<&String> = graph
+            .keys()
+            .filter(|name| !graph.values().flatten().any(|d| d == *name))
+            .filter(|name| !graph[*name].is_empty())
+            .collect();
+        roots.sort();
+        let mut lines: Vec<String> = Vec::new();
+        for root in roots {
+            lines.push(root.clone());
+            dep_tree_lines(&graph, root, "", &mut vec![root.as_str()], &mut lines);
+        }
+        sections.push(lines.join("\n"));
+    }
+
+    if order.is_empty() {
+        sections.push("Build order: none, every project is blocked by a cycle".to_string());
+    } else {
+        sections.push(format!("Build order: {}", order.join(" → ")));
+    }
+    if !remaining.is_empty() {
+        let mut cycles: Vec<String> = Vec::new();
+        let mut seen: HashSet<&str> = HashSet::new();
+        let mut stuck: Vec<&str> = remaining.keys().copied().collect();
+        stuck.sort();
+        for start in stuck {
+            if seen.contains(start) {
+                continue;
+            }
+            // Follow unordered deps until a project repeats
+            let mut path: Vec<&str> = vec![start];
+            let mut current = start;
+            while let Some(next) = graph[current]
+                .iter()
+                .map(String::as_str)
+                .find(|d| remaining.contains_key(d))
+            {
+                if let Some(pos) = path.iter().position(|p| *p == next) {
+                    let mut cycle = path[pos..].to_vec();
+                    cycle.push(next);
+                    seen.extend(cycle.iter().copied());
+                    cycles.push(format!("✗ cycle: {}", cycle.join(" → ")));
+                    break;
+                }
+                path.push(next);
+                current = next;
+            }
+            seen.extend(path);
+        }
+        sections.push(cycles.join("\n"));
+    }
+
+    CallToolResult::success(vec![Content::text(sections.join("\n\n"))])
+}
+
+async fn handle_lock_diff(projects: Vec<PathBuf>) -> CallToolResult {
+    if projects.is_empty() {
+        return CallToolResult::success(vec![Content::text(
+            "No Elixir projects found".to_string(),
+        )]);
+    }
+
+    let timeout = Duration::from_secs(load_config().command_timeout_secs);
+    let mut sections: Vec<String> = Vec::new();
+    for project in &projects {
+        if shutdown_token().is_cancelled() {
+            sections.push("- stopped: shutting down".to_string());
+            break;
+        }
+        let project_name = project
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| project.display().to_string());
+
+        let Ok(lock) = fs::read_to_string(project.join("mix.lock")) else {
+            sections.push(format!("- {} (no mix.lock)", project_name));
+            continue;
+        };
+        let mix_exs = fs::read_to_string(project.join("mix.exs")).unwrap_or_default();
+        let (locked, transitive) = parse_mix_lock(&lock);
+        let direct = mix_exs_deps(&mix_exs);
+
+        // hex.outdated exits 1 when anything is outdated, so read stdout regardless
+        let mut outdated_error = None;
+        let latest: HashMap<String, String> = match run_with_timeout(
+            Command::new("mix")
+                .args(["hex.outdated", "--all"])
+                .current_dir(project),
+            timeout,
+        )
+        .await
+        {
+            Ok(o) if !o.stdout.is_empty() => String::from_utf8_lossy(&o.stdout)
+                .lines()
+                .filter_map(|line| {
+                    let mut cols = line.split_whitespace();
+                    let (name, _current, latest) = (cols.next()?, cols.next()?, cols.next()?);
+                    locked
+                        .contains_key(name)
+                        .then(|| (name.to_string(), latest.to_string()))
+                })
+                .collect(),
+            Ok(o) => {
+                outdated_error = Some(first_error_line(&o));
+                HashMap::new()
+            }
+            Err(e) => {
+                outdated_error = Some(e);
+                HashMap::new()
+            }
+        };
+
+        let mut names: Vec<&String> = locked.keys().collect();
+        names.sort();
+        let changed: Vec<String> = names
+            .iter()
+            .filter_map(|name| {
+                let current = &locked[*name];
+                let newest = latest.get(*name)?;
+                (newest != current).then(|| format!("  {} {} → {}", name, current, newest))
+            })
+            .collect();
+        let stale: Vec<String> = names
+            .iter()
+            .filter(|name| !direct.contains(**name) && !transitive.contains(**name))
+            .map(|name| format!("  - {} {}", name, locked[*name]))
+            .collect();
+        let mut unresolved: Vec<String> = direct
+            .iter()
+            .filter(|name| !locked.contains_key(*name))
+            .map(|name| format!("  - {}", name))
+            .collect();
+        unresolved.sort();
+
+        let mut lines = vec![format!("## {}", project_name)];
+        if let Some(e) = outdated_error {
+            lines.push(format!(
+                "✗ mix hex.outdated failed, latest versions unknown: {}",
+                e
+            ));
+        } else if changed.is_empty() && stale.is_empty() && unresolved.is_empty() {
+            lines.push("✓ mix.lock is up to date".to_string());
+        }
+        for (title, entries) in [
+            ("Newer versions available (locked → latest):", changed),
+            ("Stale (locked but no longer required):", stale),
+            ("Unresolved (in mix.exs but not locked):", unresolved),
+        ] {
+            if !entries.is_empty() {
+                lines.push(title.to_string());
+                lines.extend(entries);
+            }
+        }
+        sections.push(lines.join("\n"));
+    }
+
+    CallToolResult::success(vec![Content::text(sections.join("\n\n"))])
+}
+
+async fn handle_run_in_all(projects: Vec<PathBuf>, req: &ElixirProjectsRequest) -> CallToolResult {
+    let Some(command) = req.command.as_deref() else {
+        return CallToolResult::success(vec![Content::text(
+            "Error: 'command' is required for run_in_all action".to_string(),
+        )]);
+    };
+    if let Err(e) = check_allowed_command(command) {
+        return CallToolResult::success(vec![Content::text(e)]);
+    }
+    if projects.is_empty() {
+        return CallToolResult::success(vec![Content::text(
+            "No Elixir projects found".to_string(),
+        )]);
+    }
+
+    let timeout = Duration::from_secs(load_config().command_timeout_secs);
+    let mut results: Vec<String> = Vec::new();
+    for project in &projects {
+        if shutdown_token().is_cancelled() {
+            results.push("- stopped: shutting down".to_string());
+            break;
+        }
+        let project_name = project
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| project.display().to_string());
+
+        let output = run_with_timeout(
+            Command::new("sh")
+                .args(["-c", command])
+                .current_dir(project),
+            timeout,
+        )
+        .await;
+        match output {
+            Ok(o) => {
+                let status = if o.status.success() { "✓" } else { "✗" };
+                let code = o
+                    .status
+                    .code()
+                    .map_or_else(|| "killed".to_string(), |c| format!("exit {}", c));
+                results.push(format!("{} {} ({})", status, project_name, code));
+                results.push(verbose_details(&o));
+            }
+            Err(e) => results.push(format!("✗ {} {}", project_name, e)),
+        }
+    }
+
+    CallToolResult::success(vec![Content::text(format!(
+        "Ran '{}' in {} projects:\n{}",
+        command,
+        projects.len(),
+        results.join("\n")
+    ))])
+}
+
+async fn handle_version_matrix(projects: Vec<PathBuf>) -> CallToolResult {
+    if projects.is_empty() {
+        return CallToolResult::success(vec![Content::text(
+            "No Elixir projects found".to_string(),
+        )]);
+    }
+
+    // "Erlang/OTP 26 [erts-14.2.1] ..." then "Elixir 1.16.2 (compiled with Erlang/OTP 26)"
+    let installed = Command::new("elixir")
+        .arg("--version")
+        .cancellable_output()
+        .await
+        .ok()
+        .filter(|o| o.status.success())
+        .map(|o| String::from_utf8_lossy(&o.stdout).to_string())
+        .unwrap_or_default();
+    let word_after = |prefix: &str| {
+        installed
+            .lines()
+            .find_map(|line| line.strip_prefix(prefix))
+            .and_then(|rest| rest.split_whitespace().next())
+            .map(str::to_string)
+    };
+    let elixir = word_after("Elixir ");
+    let otp = word_after("Erlang/OTP ");
+
+    let mut rows: Vec<[String; 4]> = vec![[
+        "Project".to_string(),
+        "Elixir".to_string(),
+        "OTP".to_string(),
+        "Status".to_string(),
+    ]];
+    let elixir_req_re =
+        Regex::new(r#"elixir:\s*"([^"]+)""#).expect("valid elixir requirement regex");
+    let mut drifted = 0;
+    for project in &projects {
+        let project_name = project
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| project.display().to_string());
+
+        let tool_versions: HashMap<String, String> =
+            read_tool_versions(project).into_iter().collect();
+        let read_pin = |file: &str| {
+            fs::read_to_string(project.join(file))
+                .ok()
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+        };
+        // "1.16.2-otp-26" pins both Elixir and the OTP it was built for
+        let elixir_pin = tool_versions
+            .get("elixir")
+            .cloned()
+            .or_else(|| read_pin(".elixir-version"));
+        let (elixir_pin, otp_from_elixir) = match elixir_pin {
+            Some(pin) => match pin.split_once("-otp-") {
+                Some((version, otp)) => (Some(version.to_string()), Some(otp.to_string())),
+                None => (Some(pin), None),
+            },
+            None => (None, None),
+        };
+        let otp_pin = tool_versions
+            .get("erlang")
+            .cloned()
+            .or_else(|| read_pin(".erlang-version"))
+            .or(otp_from_elixir);
+        let mix_exs = fs::read_to_string(project.join("mix.exs")).unwrap_or_default();
+        let elixir_req = elixir_req_re.captures(&mix_exs).map(|c| c[1].to_string());
+
+        let mut problems: Vec<String> = Vec::new();
+        if let (Some(pin), Some(installed)) = (&elixir_pin, &elixir)
+            && !version_has_prefix(installed, pin)
+        {
+            problems.push(format!("Elixir {} pinned", pin));
+        }
+        if let (Some(req), Some(installed)) = (&elixir_req, &elixir)
+            && elixir_requirement_matches(req, installed) == Some(false)
+        {
+            problems.push(format!("mix.exs needs {}", req));
+        }
+        if let (Some(pin), Some(installed)) = (&otp_pin, &otp)
+            && pin.split('.').next() != Some(installed.as_str())
+        {
+            problems.push(format!("OTP {} pinned", pin));
+        }
+
+        let status = if elixir.is_none() {
+            "? elixir not installed".to_string()
+        } else if problems.is_empty() {
+            "✓".to_string()
+        } else {
+            drifted += 1;
+            format!("✗ {}", problems.join(", "))
+        };
+        let elixir_column = match (elixir_pin, elixir_req) {
+            (Some(pin), Some(req)) => format!("{} ({})", pin, req),
+            (Some(pin), None) => pin,
+            (None, Some(req)) => req,
+            (None, None) => "-".to_string(),
+        };
+        rows.push([
+            project_name,
+            elixir_column,
+            otp_pin.unwrap_or_else(|| "-".to_string()),
+            status,
+        ]);
+    }
+
+    let widths: Vec<usize> = (0..3)
+        .map(|i| rows.iter().map(|r| r[i].chars().count()).max().unwrap_or(0))
+        .collect();
+    let table: Vec<String> = rows
+        .iter()
+        .map(|r| {
+            format!(
+                "{:<w0$}  {:<w1$}  {:<w2$}  {}",
+                r[0],
+                r[1],
+                r[2],
+                r[3],
+                w0 = widths[0],
+                w1 = widths[1],
+                w2 = widths[2]
+            )
+        })
+        .collect();
+
+    CallToolResult::success(vec![Content::text(format!(
+        "Installed: Elixir {}, OTP {} ({} of {} projects drift)\n\n{}",
+        elixir.as_deref().unwrap_or("not found"),
+        otp.as_deref().unwrap_or("not found"),
+        drifted,
+        projects.len(),
+        table.join("\n")
+    ))])
+}
+
+async fn handle_git_squash_local(
+    projects: Vec<PathBuf>,
+    req: &ElixirProjectsRequest,
+) -> CallToolResult {
+    let projects = match single_project(projects, req, "git_squash_local") {
+        Ok(project) => vec![project],
+        Err(e) => return CallToolResult::success(vec![Content::text(e)]),
+    };
+
+    let mut results: Vec<String> = Vec::new();
+    for project in &projects {
+        if shutdown_token().is_cancelled() {
+            results.push("- stopped: shutting down".to_string());
+            break;
+        }
+        let project_name = project
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| project.display().to_string());
+        results.push(
+            match squash_unpushed(project, req.commit_message.as_deref()).await {
+                Ok(summary) => format!("✓ {} {}", project_name, summary),
+                Err(e) => format!("✗ {} {}", project_name, e),
+            },
+        );
+    }
+
+    CallToolResult::success(vec![Content::text(results.join("\n"))])
+}
+
+async fn handle_coverage_badge(projects: Vec<PathBuf>) -> CallToolResult {
+    if projects.is_empty() {
+        return CallToolResult::success(vec![Content::text(
+            "No Elixir projects found".to_string(),
+        )]);
+    }
+
+    let badge_re = Regex::new(r"https://img\.shields\.io/badge/coverage-([\d.]+)%25-\w+")
+        .expect("valid badge regex");
+    let timeout = Duration::from_secs(load_config().command_timeout_secs);
+    let mut results: Vec<String> = Vec::new();
+    for project in &projects {
+        if shutdown_token().is_cancelled() {
+            results.push("- stopped: shutting down".to_string());
+            break;
+        }
+        let project_name = project
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| project.display().to_string());
+
+        let readme_path = project.join("README.md");
+        let readme = fs::read_to_string(&readme_path).unwrap_or_default();
+        let Some(old) = badge_re.captures(&readme).map(|c| c[1].to_string()) else {
+            results.push(format!("- {} (no coverage badge)", project_name));
+            continue;
+        };
+
+        let report_path = project.join("cover/excoveralls.json");
+        // Don't let a report from an earlier run pass for this one
+        let _ = fs::remove_file(&report_path);
+        let output = run_with_timeout(
+            Command::new("mix")
+                .arg("coveralls.json")
+                .env("MIX_ENV", "test")
+                .current_dir(project),
+            timeout,
+        )
+        .await;
+        if let Err(e) = output {
+            results.push(format!("✗ {} {}", project_name, e));
+            continue;
+        }
+        // Failing tests still write the report, so only a missing file is fatal
+        let Some(coverage) = fs::read_to_string(&report_path)
+            .ok()
+            .and_then(|json| coveralls_percentage(&json))
+        else {
+            let reason = match output {
+                Ok(o) if !o.status.success() => first_error_line(&o),
+                _ => "cover/excoveralls.json not found".to_string(),
+            };
+            results.push(format!("✗ {} {}", project_name, reason));
+            continue;
+        };
+
+        let color = match coverage {
+            c if c >= 90.0 => "brightgreen",
+            c if c >= 80.0 => "green",
+            c if c >= 70.0 => "yellowgreen",
+            c if c >= 60.0 => "yellow",
+            c if c >= 50.0 => "orange",
+            _ => "red",
+        };
+        let badge = format!(
+            "https://img.shields.io/badge/coverage-{:.1}%25-{}",
+            coverage, color
+        );
+        let updated = badge_re.replace_all(&readme, badge.as_str());
+        match fs::write(&readme_path, updated.as_bytes()) {
+            Ok(()) => results.push(format!("✓ {} {}% → {:.1}%", project_name, old, coverage)),
+            Err(e) => results.push(format!("✗ {} {}", project_name, e)),
+        }
+    }
+
+    CallToolResult::success(vec![Content::text(results.join("\n"))])
+}
+
+fn handle_port_conflicts(projects: Vec<PathBuf>) -> CallToolResult {
+    if projects.is_empty() {
+        return CallToolResult::success(vec![Content::text(
+            "No Elixir projects found".to_string(),
+        )]);
+    }
+
+    let mut by_port: HashMap<u16, Vec<String>> = HashMap::new();
+    let mut no_port: Vec<String> = Vec::new();
+    for project in &projects {
+        let project_name = project
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| project.display().to_string());
+        match dev_http_port(project) {
+            Some(port) => by_port.entry(port).or_default().push(project_name),
+            None => no_port.push(project_name),
+        }
+    }
+
+    let mut conflicts: Vec<(u16, Vec<String>)> = by_port
+        .iter()
+        .filter(|(_, names)| names.len() > 1)
+        .map(|(port, names)| (*port, names.clone()))
+        .collect();
+    conflicts.sort();
+
+    let mut text = if conflicts.is_empty() {
+        format!(
+            "✓ No port conflicts across {} projects with a dev port",
+            projects.len() - no_port.len()
+        )
+    } else {
+        let lines: Vec<String> = conflicts
+            .iter()
+            .map(|(port, names)| format!("✗ port {}: {}", port, names.join(", ")))
+            .collect();
+        format!("Ports used by more than one project:\n{}", lines.join("\n"))
+    };
+    if !no_port.is_empty() {
+        text.push_str(&format!(
+            "\n\nNo HTTP port configured: {}",
+            no_port.join(", ")
+        ));
+    }
+
+    CallToolResult::success(vec![Content::text(text)])
+}
+
+async fn handle_archive_deps(
+    projects: Vec<PathBuf>,
+    req: &ElixirProjectsRequest,
+) -> CallToolResult {
+    if projects.is_empty() {
+        return CallToolResult::success(vec![Content::text(
+            "No Elixir projects found".to_string(),
+        )]);
+    }
+
+    let remove_build = req.remove_build.unwrap_or(false);
+    let reclaimable = |project: &Path| {
+        let build = if remove_build {
+            dir_size(&project.join("_build"))
+        } else {
+            0
+        };
+        dir_size(&project.join("deps")) + build
+    };
+
+    if !req.confirm.unwrap_or(false) {
+        let sizes: Vec<(&PathBuf, u64)> = projects.iter().map(|p| (p, reclaimable(p))).collect();
+        let total: u64 = sizes.iter().map(|(_, size)| size).sum();
+        let preview: Vec<String> = sizes
+            .iter()
+            .map(|(p, size)| format!("  {} ({:.1} MiB)", p.display(), to_mib(*size)))
+            .collect();
+        return CallToolResult::success(vec![Content::text(format!(
+            "Would free {:.1} MiB from {} projects:\n{}\n\nRe-run with confirm: true to clean.",
+            to_mib(total),
+            projects.len(),
+            preview.join("\n")
+        ))]);
+    }
+
+    let timeout = Duration::from_secs(load_config().command_timeout_secs);
+    let mut results: Vec<String> = Vec::new();
+    let mut total_freed = 0;
+    for project in &projects {
+        if shutdown_token().is_cancelled() {
+            results.push("- stopped: shutting down".to_string());
+            break;
+        }
+        let project_name = project
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| project.display().to_string());
+
+        let before = reclaimable(project);
+        let output = run_with_timeout(
+            Command::new("mix")
+                .args(["deps.clean", "--all"])
+                .current_dir(project),
+            timeout,
+        )
+        .await;
+        match output {
+            Ok(o) if o.status.success() => {}
+            Ok(o) => {
+                results.push(format!("✗ {} {}", project_name, first_error_line(&o)));
+                continue;
+            }
+            Err(e) => {
+                results.push(format!("✗ {} {}", project_name, e));
+                continue;
+            }
+        }
+
+        let build_dir = project.join("_build");
+        if remove_build
+            && build_dir.exists()
+            && let Err(e) = fs::remove_dir_all(&build_dir)
+        {
+            results.push(format!("✗ {} failed to remove _build: {}", project_name, e));
+            continue;
+        }
+
+        let freed = before.saturating_sub(reclaimable(project));
+        total_freed += freed;
+        results.push(format!("✓ {} freed {:.1} MiB", project_name, to_mib(freed)));
+    }
+
+    results.push(format!("\nFreed {:.1} MiB in total", to_mib(total_freed)));
+    CallToolResult::success(vec![Content::text(results.join("\n"))])
+}
+
+fn handle_check_env(projects: Vec<PathBuf>) -> CallToolResult {
+    if projects.is_empty() {
+        return CallToolResult::success(vec![Content::text(
+            "No Elixir projects found".to_string(),
+        )]);
+    }
+
+    let secret_re =
+        Regex::new(r"(?i)(secret|key|password|token)\s*=\s*\S{16,}").expect("valid secret regex");
+    let mut results: Vec<String> = Vec::new();
+    let mut flagged_files = 0;
+    for project in &projects {
+        let project_name = project
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| project.display().to_string());
+
+        let found: Vec<&str> = ENV_FILES
+            .iter()
+            .copied()
+            .filter(|name| project.join(name).is_file())
+            .collect();
+        if found.is_empty() {
+            results.push(format!("- {} (no env files)", project_name));
+            continue;
+        }
+
+        results.push(project_name);
+        for name in found {
+            let Ok(contents) = fs::read_to_string(project.join(name)) else {
+                results.push(format!("  ✗ {} unreadable", name));
+                continue;
+            };
+            // Report line numbers only, never the values themselves
+            let suspicious: Vec<String> = contents
+                .lines()
+                .enumerate()
+                .filter(|(_, line)| secret_re.is_match(line))
+                .map(|(i, _)| (i + 1).to_string())
+                .collect();
+            let line_count = contents.lines().count();
+            if suspicious.is_empty() {
+                results.push(format!("  ✓ {} (lines: {})", name, line_count));
+            } else {
+                flagged_files += 1;
+                results.push(format!(
+                    "  ✗ {} (lines: {}) possible hardcoded secrets on line {}",
+                    name,
+                    line_count,
+                    suspicious.join(", ")
+                ));
+            }
+        }
+    }
+
+    results.push(format!(
+        "\nEnv files with possible hardcoded secrets: {}",
+        flagged_files
+    ));
+    CallToolResult::success(vec![Content::text(results.join("\n"))])
+}
+
+// Helper functions
+
+/// Line coverage from an ExCoveralls JSON report, where each source file has
+/// a `coverage` array of hit counts with `null` for irrelevant lines.
+fn coveralls_percentage(json: &str) -> Option<f64> {
+    let report: serde_json::Value = serde_json::from_str(json).ok()?;
+    let (mut relevant, mut covered) = (0usize, 0usize);
+    for file in report["source_files"].as_array()? {
+        for hits in file["coverage"].as_array().into_iter().flatten() {
+            if let Some(hits) = hits.as_u64() {
+                relevant += 1;
+                if hits > 0 {
+                    covered += 1;
+                }
+            }
+        }
+    }
+    (relevant > 0).then(|| covered as f64 * 100.0 / relevant as f64)
+}
+
+/// Soft-reset to the upstream branch and commit everything unpushed as one
+/// commit, using the oldest unpushed subject when no message is given.
+async fn squash_unpushed(project: &Path, message: Option<&str>) -> Result<String, String> {
+    let upstream = git_stdout(project, &["rev-parse", "--abbrev-ref", "@{u}"])
+        .await
+        .ok_or("no upstream branch to squash onto")?;
+    let count: usize = git_stdout(project, &["rev-list", "--count", "@{u}..HEAD"])
+        .await
+        .and_then(|c| c.parse().ok())
+        .unwrap_or(0);
+    if count < 2 {
+        return Err(format!(
+            "{} unpushed commit(s) ahead of {}, nothing to squash",
+            count, upstream
+        ));
+    }
+    // Resetting onto a diverged upstream would make the squash commit revert
+    // the upstream commits the branch lacks
+    let behind: usize = git_stdout(project, &["rev-list", "--count", "HEAD..@{u}"])
+        .await
+        .and_then(|c| c.parse().ok())
+        .ok_or("could not compare with upstream")?;
+    if behind > 0 {
+        return Err(format!(
+            "has diverged from {} ({} commit(s) behind); rebase first",
+            upstream, behind
+        ));
+    }
+
+    // Staged changes would silently end up in the squashed commit
+    let staged = Command::new("git")
+        .args(["diff", "--cached", "--quiet"])
+        .current_dir(project)
+        .cancellable_output()
+        .await
+        .map_err(|e| e.to_string())?;
+    if !staged.status.success() {
+        return Err("has staged changes; commit or unstage them first".to_string());
+    }
+
+    let message = match message {
+        Some(message) => message.to_string(),
+        None => git_stdout(project, &["log", "--reverse", "--format=%s", "@{u}..HEAD"])
+            .await
+            .and_then(|subjects| subjects.lines().next().map(str::to_string))
+            .unwrap_or_else(|| "Squashed commits".to_string()),
+    };
+    let old_head = git_stdout(project, &["rev-parse", "HEAD"])
+        .await
+        .ok_or("could not read HEAD")?;
+
+    for args in [
+        vec!["reset", "--soft", upstream.as_str()],
+        vec!["commit", "-m", message.as_str()],
+    ] {
+        let output = Command::new("git")
+            .args(&args)
+            .current_dir(project)
+            .cancellable_output()
+            .await
+            .map_err(|e| e.to_string())?;
+        if !output.status.success() {
+            return Err(format!(
+                "git {} failed: {} (restore with 'git reset --soft {}')",
+                args[0],
+                first_error_line(&output),
+                old_head
+            ));
+        }
+    }
+
+    let new_head = git_stdout(project, &["rev-parse", "--short", "HEAD"])
+        .await
+        .unwrap_or_default();
+    Ok(format!(
+        "squashed {} commits onto {} into {} \"{}\"",
+        count, upstream, new_head, message
+    ))
+}
+
+/// Whether `version` matches a pin that may leave out trailing components,
+/// e.g. "1.16.2" against "1.16".
+fn version_has_prefix(version: &str, pin: &str) -> bool {
+    version == pin || version.starts_with(&format!("{}.", pin))
+}
+
+/// Check an Elixir requirement such as "~> 1.14" or ">= 1.12.0 and < 2.0.0"
+/// against `version`, or `None` when either cannot be parsed. Elixir's `~>`
+/// differs from Cargo's `~`, so it is translated into explicit bounds.
+fn elixir_requirement_matches(requirement: &str, version: &str) -> Option<bool> {
+    let version = semver::Version::parse(version).ok()?;
+    let clause_matches = |clause: &str| -> Option<bool> {
+        let bounds: Vec<String> = clause
+            .split(" and ")
+            .map(|part| {
+                let part = part.trim();
+                let Some(base) = part.strip_prefix("~>") else {
+                    return Some(part.replace("==", "="));
+                };
+                let base = base.trim();
+                let nums: Vec<u64> = base
+                    .split('.')
+                    .map(|n| n.parse().ok())
+                    .collect::<Option<_>>()?;
+                match nums.as_slice() {
+                    [major, minor] => Some(format!(">={}.{}.0, <{}.0.0", major, minor, major + 1)),
+                    [major, minor, patch] => Some(format!(
+                        ">={}.{}.{}, <{}.{}.0",
+                        major,
+                        minor,
+                        patch,
+                        major,
+                        minor + 1
+                    )),
+                    _ => None,
+                }
+            })
+            .collect::<Option<_>>()?;
+        let req = semver::VersionReq::parse(&bounds.join(", ")).ok()?;
+        Some(req.matches(&version))
+    };
+
+    let mut any_match = false;
+    for clause in requirement.split(" or ") {
+        any_match |= clause_matches(clause)?;
+    }
+    Some(any_match)
+}
+
+/// Commands run through `sh -c` must appear verbatim in
+/// `~/.config/steve/allowed_commands.toml`.
+fn check_allowed_command(command: &str) -> Result<(), String> {
+    let allowed_path = dirs::home_dir()
+        .map(|h| h.join(".config/steve/allowed_commands.toml"))
+        .unwrap_or_else(|| PathBuf::from("allowed_commands.toml"));
+    let allowed: AllowedCommands = fs::read_to_string(&allowed_path)
+        .map_err(|_| {
+            format!(
+                "No allowlist found. Create {} with: commands = [{:?}]",
+                allowed_path.display(),
+                command
+            )
+        })
+        .and_then(|contents| {
+            toml::from_str(&contents)
+                .map_err(|e| format!("Failed to parse {}: {}", allowed_path.display(), e))
+        })?;
+    if allowed.commands.iter().any(|c| c == command) {
+        Ok(())
+    } else {
+        Err(format!(
+            "'{}' is not in {}. Allowed: {}",
+            command,
+            allowed_path.display(),
+            allowed.commands.join(", ")
+        ))
+    }
+}
+
+/// Locked version (or short git ref) per package in `mix.lock`, plus the names
+/// other locked packages require.
+fn parse_mix_lock(lock: &str) -> (HashMap<String, String>, HashSet<String>) {
+    // "jason": {:hex, :jason, "1.4.1", ...} and "foo": {:git, "url", "sha", ...}
+    let entry_re = Regex::new(r#""(\w+)":\s*\{:(?:hex,\s*:\w+|git,\s*"[^"]*"),\s*"([^"]+)""#)
+        .expect("valid lock entry regex");
+    // Requirements inside an entry: {:mime, "~> 1.0", [hex: :mime, ...]}
+    let requirement_re =
+        Regex::new(r#"\{:(\w+),\s*"[^"]*",\s*\[hex:"#).expect("valid requirement regex");
+
+    let locked = entry_re
+        .captures_iter(lock)
+        .map(|c| {
+            let version = c[2].to_string();
+            // Git refs are full commit SHAs
+            let version = if version.len() == 40 {
+                version[..7].to_string()
+            } else {
+                version
+            };
+            (c[1].to_string(), version)
+        })
+        .collect();
+    let transitive = requirement_re
+        .captures_iter(lock)
+        .map(|c| c[1].to_string())
+        .collect();
+    (locked, transitive)
+}
+
+/// Dependency names listed in the `deps` function of a `mix.exs`.
+fn mix_exs_deps(mix_exs: &str) -> HashSet<String> {
+    let Some(start) = mix_exs.find("defp deps") else {
+        return HashSet::new();
+    };
+    let body = &mix_exs[start..];
+    let body = &body[..body.find("\n  end").unwrap_or(body.len())];
+    let dep_re = Regex::new(r"\{\s*:(\w+)\s*,").expect("valid dep regex");
+    dep_re
+        .captures_iter(body)
+        .map(|c| c[1].to_string())
+        .collect()
+}
+
+/// Project name → names of other scanned projects it depends on through
+/// `path:`, `git:` or `github:` deps in its `mix.exs`.
+fn project_dep_graph(projects: &[PathBuf]) -> HashMap<String, Vec<String>> {
+    let dep_re = Regex::new(r#"\{\s*:(\w+)\s*,[^{}]*?\b(path|git|github):\s*"([^"]+)""#)
+        .expect("valid dep regex");
+    let name_of = |p: &Path| {
+        p.file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| p.display().to_string())
+    };
+    let by_path: HashMap<PathBuf, String> = projects
+        .iter()
+        .filter_map(|p| Some((p.canonicalize().ok()?, name_of(p))))
+        .collect();
+    let names: HashSet<String> = projects.iter().map(|p| name_of(p)).collect();
+
+    projects
+        .iter()
+        .map(|project| {
+            let mix_exs = fs::read_to_string(project.join("mix.exs")).unwrap_or_default();
+            let mut deps: Vec<String> = dep_re
+                .captures_iter(&mix_exs)
+                .filter_map(|c| {
+                    let target = if &c[2] == "path" {
+                        let path = project.join(&c[3]).canonicalize().ok()?;
+                        by_path.get(&path)?.clone()
+                    } else {
+                        // git@host:org/repo.git, https://host/org/repo or org/repo
+                        let repo = c[3].trim_end_matches('/').trim_end_matches(".git");
+                        let repo = repo.rsplit(['/', ':']).next().unwrap_or(repo);
+                        [repo, &c[1]]
+                            .into_iter()
+                            .find(|n| names.contains(*n))?
+                            .to_string()
+                    };
+                    Some(target)
+                })
+                .filter(|target| *target != name_of(project))
+                .collect();
+            deps.sort();
+            deps.dedup();
+            (name_of(project), deps)
+        })
+        .collect()
+}
+
+/// Append `name`'s deps as tree lines, stopping where a dep would repeat the
+/// current path.
+fn dep_tree_lines<'a>(
+    graph: &'a HashMap<String, Vec<String>>,
+    name: &str,
+    prefix: &str,
+    path: &mut Vec<&'a str>,
+    lines: &mut Vec<String>,
+) {
+    let deps = &graph[name];
+    for (i, dep) in deps.iter().enumerate() {
+        let last = i + 1 == deps.len();
+        let branch = if last { "└── " } else { "├── " };
+        if path.contains(&dep.as_str()) {
+            lines.push(format!("{}{}{} (cycle)", prefix, branch, dep));
+            continue;
+        }
+        lines.push(format!("{}{}{}", prefix, branch, dep));
+        path.push(dep);
+        let child_prefix = format!("{}{}", prefix, if last { "    " } else { "│   " });
+        dep_tree_lines(graph, dep, &child_prefix, path, lines);
+        path.pop();
+    }
+}
+
+/// Group a compiler warning message such as `variable "x" is unused` into a
+/// broad category.
+fn warning_category(message: &str) -> &'static str {
+    let message = message.trim();
+    if message.contains("deprecated") {
+        "deprecated API"
+    } else if message.starts_with("variable") && message.contains("is unused") {
+        "unused variable"
+    } else if message.starts_with("unused alias") || message.starts_with("unused import") {
+        "unused alias/import"
+    } else if message.contains("was set but never used") {
+        "unused module attribute"
+    } else if message.contains("is unused") {
+        "unused function"
+    } else if message.contains("is undefined") || message.contains("undefined function") {
+        "undefined function/module"
+    } else if message.contains("will never match") || message.contains("cannot match") {
+        "clause never matches"
+    } else if message.contains("type") && message.contains("incompatible") {
+        "type mismatch"
+    } else {
+        "other"
+    }
+}
+
+/// One row of `mix hex.search` output, keyed by lowercased column header.
+struct HexPackage(HashMap<String, String>);
+
+impl HexPackage {
+    fn get(&self, column: &str) -> &str {
+        self.0.get(column).map(String::as_str).unwrap_or("")
+    }
+}
+
+/// Split the `mix hex.search` table into rows, using the header line to find
+/// where each column starts. Which columns exist depends on the Hex version.
+fn parse_hex_search(output: &str) -> Vec<HexPackage> {
+    let mut lines = output.lines().skip_while(|l| !l.starts_with("Package"));
+    let Some(header) = lines.next() else {
+        return Vec::new();
+    };
+
+    // Columns are padded with spaces, so a column starts after two or more
+    let header: Vec<char> = header.chars().collect();
+    let starts: Vec<usize> = (0..header.len())
+        .filter(|&i| header[i] != ' ' && (i == 0 || (i >= 2 && header[i - 2..i] == [' ', ' '])))
+        .collect();
+    let field = |chars: &[char], n: usize| -> String {
+        let start = starts[n].min(chars.len());
+        let end = starts
+            .get(n + 1)
+            .map_or(chars.len(), |&e| e.min(chars.len()));
+        chars[start..end]
+            .iter()
+            .collect::<String>()
+            .trim()
+            .to_string()
+    };
+    let names: Vec<String> = (0..starts.len())
+        .map(|n| field(&header, n).to_lowercase())
+        .collect();
+
+    lines
+        .filter(|l| !l.trim().is_empty())
+        .map(|line| {
+            let chars: Vec<char> = line.chars().collect();
+            HexPackage(
+                names
+                    .iter()
+                    .enumerate()
+                    .map(|(n, name)| (name.clone(), field(&chars, n)))
+                    .collect(),
+            )
+        })
+        .collect()
+}
+
+/// Names under a `nix flake show --json` output such as `devShells`, as
+/// comma-separated `<system>.<name>` entries.
+fn flake_outputs(flake: &serde_json::Value, kind: &str) -> String {
+    let names: Vec<String> = flake[kind]
+        .as_object()
+        .into_iter()
+        .flatten()
+        .filter_map(|(system, outputs)| Some((system, outputs.as_object()?)))
+        .flat_map(|(system, outputs)| {
+            outputs
+                .keys()
+                .map(move |name| format!("{}.{}", system, name))
+        })
+        .collect();
+    if names.is_empty() {
+        "none".to_string()
+    } else {
+        names.join(", ")
+    }
+}
+
+/// Installed versions per tool from `mise ls --json`.
+async fn mise_installed_versions() -> Result<HashMap<String, Vec<String>>, String> {
+    let output = Command::new("mise")
+        .args(["ls", "--json"])
+        .cancellable_output()
+        .await
+        .map_err(|e| format!("Failed to run mise: {}", e))?;
+    if !output.status.success() {
+        return Err(format!("✗ mise ls failed: {}", first_error_line(&output)));
+    }
+
+    // {"elixir": [{"version": "1.16.2-otp-26", "installed": true, ...}], ...}
+    let tools: HashMap<String, Vec<serde_json::Value>> = serde_json::from_slice(&output.stdout)
+        .map_err(|e| format!("Failed to parse mise output: {}", e))?;
+    Ok(tools
+        .into_iter()
+        .map(|(tool, entries)| {
+            let versions = entries
+                .iter()
+                .filter(|e| e["installed"].as_bool() == Some(true))
+                .filter_map(|e| e["version"].as_str().map(str::to_string))
+                .collect();
+            (tool, versions)
+        })
+        .collect())
+}
+
+/// Tool versions a project asks for: the `[tools]` table of `.mise.toml`, or
+/// `.tool-versions` when there is none.
+fn mise_required_versions(project: &Path) -> Vec<(String, String)> {
+    let Ok(content) = fs::read_to_string(project.join(".mise.toml")) else {
+        return read_tool_versions(project);
+    };
+    let Ok(config) = content.parse::<toml::Table>() else {
+        return Vec::new();
+    };
+
+    // Values are "1.16", ["1.16", "1.15"] or { version = "1.16", ... }
+    config
+        .get("tools")
+        .and_then(|tools| tools.as_table())
+        .map(|tools| {
+            tools
+                .iter()
+                .filter_map(|(tool, value)| {
+                    let version = match value {
+                        toml::Value::String(v) => Some(v.as_str()),
+                        toml::Value::Array(vs) => vs.first().and_then(|v| v.as_str()),
+                        toml::Value::Table(t) => t.get("version").and_then(|v| v.as_str()),
+                        _ => None,
+                    }?;
+                    Some((tool.clone(), version.to_string()))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// The endpoint port configured as `http: [port: N]` in `config/dev.exs`, or
+/// in `config/config.exs` when dev.exs doesn't set one. A `PORT` env lookup
+/// counts as its default, e.g. `System.get_env("PORT") || "4001"`.
+fn dev_http_port(project: &Path) -> Option<u16> {
+    let port_re = Regex::new(r#"http:\s*\[[^\]]*?port:\s*(?:[^,\]]*?"PORT"\)\s*\|\|\s*"?)?(\d+)"#)
+        .expect("valid port regex");
+    ["config/dev.exs", "config/config.exs"]
+        .iter()
+        .find_map(|file| {
+            let content = fs::read_to_string(project.join(file)).ok()?;
+            port_re.captures(&content)?[1].parse().ok()
+        })
+}
+
+/// Pull requests/sec, error rate and p99 latency out of `hey`'s report.
+fn summarize_hey_output(stdout: &str, total_requests: &str) -> String {
+    let value_after = |prefix: &str| {
+        stdout
+            .lines()
+            .map(str::trim)
+            .find_map(|line| line.strip_prefix(prefix))
+            .map(|rest| rest.trim().to_string())
+    };
+
+    let requests_per_sec = value_after("Requests/sec:").unwrap_or_else(|| "?".to_string());
+    let p99 = value_after("99% in").unwrap_or_else(|| "?".to_string());
+
+    // Error lines look like "  [12]\tGet ...: connection refused"
+    let errors: u32 = stdout
+        .lines()
+        .skip_while(|line| !line.starts_with("Error distribution:"))
+        .filter_map(|line| {
+            let count = line.trim().strip_prefix('[')?.split(']').next()?;
+            count.parse::<u32>().ok()
+        })
+        .sum();
+    let total: f64 = total_requests.parse().unwrap_or(0.0);
+    let error_rate = if total > 0.0 {
+        f64::from(errors) / total * 100.0
+    } else {
+        0.0
+    };
+
+    format!(
+        "  Requests/sec: {}\n  Error rate: {:.1}%\n  p99 latency: {}",
+        requests_per_sec, error_rate, p99
+    )
+}
+
+/// Summarize the output of a project's `mix loadtest` task, which has no fixed
+/// format: look for `Requests/sec` (or `req/s`, `rps`), an error rate or error
+/// count, and a p99 latency. `None` when none of them are present.
+fn summarize_loadtest_output(stdout: &str, total_requests: &str) -> Option<String> {
+    let find = |pattern: &str| {
+        Regex::new(pattern)
+            .expect("valid loadtest regex")
+            .captures(stdout)
+            .map(|c| c[1].trim().to_string())
+    };
+
+    let requests_per_sec = find(r"(?i)(?:requests/sec|req/s|rps)\W*([\d.]+)");
+    let p99 = find(r"(?i)(?:p99|99%)(?:\s+in)?\W*([\d.]+\s*[a-zµ]*)");
+    let error_rate = find(r"(?i)error\s*rate\W*([\d.]+)").or_else(|| {
+        let errors: f64 = find(r"(?i)\berrors?\s*[:=]\s*(\d+)")?.parse().ok()?;
+        let total: f64 = total_requests.parse().ok().filter(|t| *t > 0.0)?;
+        Some(format!("{:.1}", errors / total * 100.0))
+    });
+
+    if requests_per_sec.is_none() && p99.is_none() && error_rate.is_none() {
+        return None;
+    }
+    let or_unknown = |value: Option<String>| value.unwrap_or_else(|| "?".to_string());
+    Some(format!(
+        "  Requests/sec: {}\n  Error rate: {}%\n  p99 latency: {}",
+        or_unknown(requests_per_sec),
+        or_unknown(error_rate),
+        or_unknown(p99)
+    ))
+}
+
+/// Parse `(ahead, behind)` from the `# branch.ab +N -M` line of `git status --porcelain=v2`.
+fn parse_ahead_behind(stdout: &str) -> (u32, u32) {
+    stdout
+        .lines()
+        .find_map(|line| line.strip_prefix("# branch.ab "))
+        .and_then(|ab| {
+            let (ahead, behind) = ab.split_once(' ')?;
+            Some((
+                ahead.trim_start_matches('+').parse().ok()?,
+                behind.trim_start_matches('-').parse().ok()?,
+            ))
+        })
+        .unwrap_or((0, 0))
+}
+
+/// Total size in bytes of all files below `path`.
+fn dir_size(path: &Path) -> u64 {
+    WalkDir::new(path)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .filter_map(|e| e.metadata().ok())
+        .map(|m| m.len())
+        .sum()
+}
+
+fn to_mib(bytes: u64) -> f64 {
+    bytes as f64 / (1024.0 * 1024.0)
+}
+
+fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", size, UNITS[unit])
+}
+
+/// Extract the percentage from ExCoveralls' `[TOTAL]  85.3%` line.
+fn parse_coverage_total(stdout: &str) -> Option<f64> {
+    stdout
+        .lines()
+        .find(|line| line.trim_start().starts_with("[TOTAL]"))
+        .and_then(|line| line.split_whitespace().nth(1))
+        .and_then(|percent| percent.trim_end_matches('%').parse().ok())
+}
+
+/// Parse the retired-package table printed by `mix hex.audit`.
+fn parse_hex_audit(stdout: &str) -> Vec<(u8, String)> {
+    stdout
+        .lines()
+        .skip_while(|line| !line.starts_with("Dependency"))
+        .skip(1)
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            let package = parts.next()?;
+            let version = parts.next()?;
+            let reason = parts.collect::<Vec<_>>().join(" ");
+            let rank = if reason.contains("security") { 0 } else { 1 };
+            Some((
+                rank,
+                format!("hex.audit: {} {} retired {}", package, version, reason),
+            ))
+        })
+        .collect()
+}
+
+/// Parse `mix sobelow --format json` findings grouped by confidence.
+fn parse_sobelow(stdout: &str) -> Vec<(u8, String)> {
+    let Some(json) = stdout
+        .find('{')
+        .and_then(|start| serde_json::from_str::<serde_json::Value>(&stdout[start..]).ok())
+    else {
+        return Vec::new();
+    };
+
+    let groups = [
+        (0, "high_confidence"),
+        (1, "medium_confidence"),
+        (2, "low_confidence"),
+    ];
+
+    let mut findings = Vec::new();
+    for (rank, key) in groups {
+        let Some(entries) = json["findings"][key].as_array() else {
+            continue;
+        };
+        for entry in entries {
+            findings.push((
+                rank,
+                format!(
+                    "sobelow: {} in {}:{}",
+                    entry["type"].as_str().unwrap_or("unknown"),
+                    entry["file"].as_str().unwrap_or("?"),
+                    entry["line"]
+                ),
+            ));
+        }
+    }
+    findings
+}
+
+/// The first `count` sections of a changelog starting at `## [` or `## v` headings.
+fn recent_changelog_sections(content: &str, count: usize) -> String {
+    let mut sections_seen = 0;
+    let mut lines: Vec<&str> = Vec::new();
+
+    for line in content.lines() {
+        if line.starts_with("## [") || line.starts_with("## v") {
+            sections_seen += 1;
+            if sections_seen > count {
+                break;
+            }
+        }
+        if sections_seen > 0 {
+            lines.push(line);
+        }
+    }
+
+    lines.join("\n").trim().to_string()
+}
+
+/// Trimmed stdout of a successful git command, or `None` if it failed.
+async fn git_stdout(project: &Path, args: &[&str]) -> Option<String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(project)
+        .cancellable_output()
+        .await
+        .ok()?;
+
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// `Command::output` that gives up when Steve is shutting down. The child is
+/// killed on drop, so an abandoned `mix` or `git` does not outlive Steve.
+pub(crate) trait CancellableOutput {
+    async fn cancellable_output(&mut self) -> std::io::Result<Output>;
+}
+
+impl CancellableOutput for Command {
+    async fn cancellable_output(&mut self) -> std::io::Result<Output> {
+        self.kill_on_drop(true);
+        tokio::select! {
+            output = self.output() => output,
+            _ = shutdown_token().cancelled() => Err(std::io::Error::other("cancelled: shutting down")),
+        }
+    }
+}
+
+pub(crate) async fn run_with_timeout(
+    cmd: &mut Command,
+    timeout: Duration,
+) -> Result<Output, String> {
+    match tokio::time::timeout(timeout, cmd.cancellable_output()).await {
+        Ok(Ok(output)) => Ok(output),
+        Ok(Err(e)) => Err(e.to_string()),
+        Err(_) => Err(format!("timed out after {}s", timeout.as_secs())),
+    }
+}
+
+/// Combined stdout/stderr of a command, indented and capped at 500 chars.
+fn verbose_details(output: &Output) -> String {
+    const MAX_CHARS: usize = 500;
+
+    let combined = format!(
+        "{}\n{}",
+        String::from_utf8_lossy(&output.stdout).trim(),
+        String::from_utf8_lossy(&output.stderr).trim()
+    );
+    let combined = combined.trim();
+    let mut details: String = combined.chars().take(MAX_CHARS).collect();
+    if combined.chars().count() > MAX_CHARS {
+        details.push_str("...");
+    }
+    format!("    {}", details.replace('\n', "\n    "))
+}
+
+fn first_error_line(output: &Output) -> String {
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    stderr
+        .lines()
+        .chain(stdout.lines())
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .unwrap_or("failed")
+        .to_string()
+}
+
+fn get_cache_path() -> PathBuf {
+    dirs::home_dir()
+        .map(|h| h.join(".cache/steve/projects"))
+        .unwrap_or_else(|| PathBuf::from(".projects"))
+}
+
+fn get_export_path() -> PathBuf {
+    dirs::home_dir()
+        .map(|h| h.join(".cache/steve/projects_export.txt"))
+        .unwrap_or_else(|| PathBuf::from("projects_export.txt"))
+}
+
+fn get_ignore_path() -> PathBuf {
+    dirs::home_dir()
+        .map(|h| h.join(".cache/steve/ignored"))
+        .unwrap_or_else(|| PathBuf::from(".ignored"))
+}
+
+fn get_imported_path() -> PathBuf {
+    dirs::home_dir()
+        .map(|h| h.join(".cache/steve/imported"))
+        .unwrap_or_else(|| PathBuf::from(".imported"))
+}
+
+/// Project paths added with the import action.
+fn load_imported_projects() -> Vec<PathBuf> {
+    fs::read_to_string(get_imported_path())
+        .map(|content| content.lines().map(PathBuf::from).collect())
+        .unwrap_or_default()
+}
+
+/// `projects` plus the imported projects that still exist, sorted.
+fn merge_imported(mut projects: Vec<PathBuf>) -> Vec<PathBuf> {
+    for path in load_imported_projects() {
+        if path.join("mix.exs").exists() && !projects.contains(&path) {
+            projects.push(path);
+        }
+    }
+    projects.sort();
+    projects
+}
+
+fn load_ignored_projects() -> HashSet<String> {
+    let ignore_path = get_ignore_path();
+    if !ignore_path.exists() {
+        return HashSet::new();
+    }
+
+    fs::File::open(&ignore_path)
+        .ok()
+        .map(|file| BufReader::new(file).lines().map_while(Result::ok).collect())
+        .unwrap_or_default()
+}
+
+fn save_ignored_projects(ignored: &HashSet<String>) -> Result<(), std::io::Error> {
+    let ignore_path = get_ignore_path();
+    if let Some(parent) = ignore_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let mut file = fs::File::create(&ignore_path)?;
+    for name in ignored {
+        writeln!(file, "{}", name)?;
+    }
+    Ok(())
+}
+
+fn load_projects_from_cache() -> Option<Vec<PathBuf>> {
+    let cache_path = get_cache_path();
+    if !cache_path.exists() {
+        return None;
+    }
+
+    let file = fs::File::open(&cache_path).ok()?;
+    let mut lines = BufReader::new(file)
+        .lines()
+        .map_while(Result::ok)
+        .peekable();
+    let mut projects: Vec<PathBuf> = Vec::new();
+
+    // Version 1 caches are a bare path list; rewrite them with a header
+    let version = match lines.peek().and_then(|l| l.strip_prefix("# version=")) {
+        Some(version) => version.trim().parse::<u32>().ok()?,
+        None => 1,
+    };
+    if version > CACHE_VERSION {
+        // Written by a newer Steve; rescan rather than misread it
+        return None;
+    }
+    if version > 1 {
+        lines.next();
+    }
+    let mut needs_update = version < CACHE_VERSION;
+
+    for line in lines {
+        let path = PathBuf::from(&line);
+        if path.exists() && path.join("mix.exs").exists() {
+            projects.push(path);
+        } else {
+            needs_update = true;
+        }
+    }
+
+    // Update cache if we removed any stale entries
+    if needs_update {
+        let _ = save_projects_to_cache(&projects);
+    }
+
+    Some(projects)
+}
+
+fn save_projects_to_cache(projects: &[PathBuf]) -> Result<(), std::io::Error> {
+    let header = format!("# version={}", CACHE_VERSION);
+    write_project_list(&get_cache_path(), Some(&header), projects)
+}
+
+fn write_project_list(
+    path: &Path,
+    header: Option<&str>,
+    projects: &[PathBuf],
+) -> Result<(), std::io::Error> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let mut file = fs::File::create(path)?;
+    if let Some(header) = header {
+        writeln!(file, "{}", header)?;
+    }
+    for project in projects {
+        writeln!(file, "{}", project.display())?;
+    }
+    Ok(())
+}
+
+fn default_scan_root() -> PathBuf {
+    dirs::home_dir()
+        .map(|h| h.join("src/flt"))
+        .unwrap_or_else(|| PathBuf::from("."))
+}
+
+pub(crate) fn expand_path(path: &str) -> PathBuf {
+    if let Some(rest) = path.strip_prefix("~/") {
+        dirs::home_dir()
+            .map(|h| h.join(rest))
+            .unwrap_or_else(|| PathBuf::from(path))
+    } else {
+        PathBuf::from(path)
+    }
+}
+
+/// Directory names to skip while scanning: the request override if given,
+/// then `skip_dirs` from the config file, then the built-in defaults.
+fn resolve_skip_dirs(skip_dirs: Option<&[String]>) -> HashSet<String> {
+    skip_dirs
+        .map(<[String]>::to_vec)
+        .or_else(|| load_config().skip_dirs)
+        .unwrap_or_else(|| SKIP_DIRS.map(String::from).to_vec())
+        .into_iter()
+        .collect()
+}
+
+/// Scan depth from the request, then `scan_depth` from the config file, then
+/// the default.
+fn resolve_scan_depth(scan_depth: Option<usize>) -> usize {
+    scan_depth
+        .or_else(|| load_config().scan_depth)
+        .unwrap_or(DEFAULT_SCAN_DEPTH)
+}
+
+/// Ignore globs from the request, then `ignore_glob` from the config file.
+/// Patterns that fail to parse are dropped.
+fn resolve_ignore_globs(ignore_glob: Option<&[String]>) -> Result<Vec<glob::Pattern>, String> {
+    let (patterns, source) = match ignore_glob {
+        Some(patterns) => (patterns.to_vec(), "ignore_glob"),
+        None => (
+            load_config().ignore_glob.unwrap_or_default(),
+            "config.toml ignore_glob",
+        ),
+    };
+    patterns
+        .iter()
+        .map(|pattern| {
+            glob::Pattern::new(pattern)
+                .map_err(|e| format!("Error: invalid pattern '{}' in {}: {}", pattern, source, e))
+        })
+        .collect()
+}
+
+fn scan_elixir_projects(
+    path: Option<&str>,
+    skip_dirs: Option<&[String]>,
+    scan_depth: Option<usize>,
+    ignore_glob: Option<&[String]>,
+) -> Result<Vec<PathBuf>, String> {
+    let start_path = path.map(expand_path).unwrap_or_else(default_scan_root);
+    let ignore_globs = resolve_ignore_globs(ignore_glob)?;
+
+    if !start_path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let skip_dirs = resolve_skip_dirs(skip_dirs);
+
+    let mut projects: Vec<PathBuf> = Vec::new();
+
+    for entry in WalkDir::new(&start_path)
+        // mix.exs sits one level below the project directory
+        .max_depth(resolve_scan_depth(scan_depth) + 1)
+        .follow_links(true)
+        .into_iter()
+        .filter_entry(|e| {
+            // Skip certain directories
+            if e.file_type().is_dir()
+                && let Some(name) = e.file_name().to_str()
+                && skip_dirs.contains(name)
+            {
+                return false;
+            }
+            // Then anything matching an ignore glob
+            !ignore_globs.iter().any(|g| g.matches_path(e.path()))
+        })
+        .filter_map(|e| e.ok())
+    {
+        if entry.file_type().is_file()
+            && entry.file_name() == "mix.exs"
+            && let Some(parent) = entry.path().parent()
+        {
+            projects.push(parent.to_path_buf());
+        }
+    }
+
+    // Imported projects belong to the default root's list wherever they live
+    if path.is_none() {
+        return Ok(merge_imported(projects));
+    }
+    projects.sort();
+    Ok(projects)
+}
+
+/// Cached (non-ignored) projects whose name contains `filter`, for use by other tools.
+pub(crate) fn find_elixir_projects(filter: Option<&str>) -> Vec<PathBuf> {
+    filter_projects(
+        filter_ignored(get_elixir_projects(None, None, None, None, false).unwrap_or_default()),
+        filter,
+    )
+}
+
+/// The cached project named `name`, or the only one whose name contains it.
+fn resolve_project(name: &str) -> Result<PathBuf, String> {
+    pick_project(find_elixir_projects(Some(name)), name)
+}
+
+/// The one project a destructive action may touch: the request's `project`
+/// filter must name exactly one of `projects`, since the filter otherwise
+/// matches substrings.
+fn single_project(
+    projects: Vec<PathBuf>,
+    req: &ElixirProjectsRequest,
+    action: &str,
+) -> Result<PathBuf, String> {
+    match req.project.as_deref() {
+        Some(name) => pick_project(projects, name),
+        None => Err(format!(
+            "Error: 'project' filter is required for {} action",
+            action
+        )),
+    }
+}
+
+/// The project in `matches` named exactly `name`, or the only one.
+fn pick_project(mut matches: Vec<PathBuf>, name: &str) -> Result<PathBuf, String> {
+    if let Some(exact) = matches
+        .iter()
+        .find(|p| p.file_name().is_some_and(|n| n == name))
+    {
+        return Ok(exact.clone());
+    }
+
+    match matches.len() {
+        0 => Err(format!("✗ No project matching '{}'", name)),
+        1 => Ok(matches.remove(0)),
+        _ => Err(format!(
+            "✗ '{}' matches {} projects: {}",
+            name,
+            matches.len(),
+            matches
+                .iter()
+                .filter_map(|p| p.file_name())
+                .map(|n| n.to_string_lossy())
+                .collect::<Vec<_>>()
+                .join(", ")
+        )),
+    }
+}
+
+// Filter by project name if specified
+fn filter_projects(mut projects: Vec<PathBuf>, filter: Option<&str>) -> Vec<PathBuf> {
+    if let Some(project_filter) = filter {
+        let filter_lower = project_filter.to_lowercase();
+        projects.retain(|p| {
+            p.file_name()
+                .map(|n| n.to_string_lossy().to_lowercase().contains(&filter_lower))
+                .unwrap_or(false)
+        });
+    }
+    projects
+}
+
+/// `get_elixir_projects` for a request, sharing the result with identical
+/// calls that arrive while it is running.
+async fn get_elixir_projects_shared(
+    scans: &ProjectScans,
+    req: &ElixirProjectsRequest,
+) -> Result<Vec<PathBuf>, String> {
+    let mut hasher = DefaultHasher::new();
+    (
+        req.action,
+        &req.path,
+        &req.skip_dirs,
+        req.scan_depth,
+        &req.ignore_glob,
+    )
+        .hash(&mut hasher);
+    let key = format!("{:x}", hasher.finish());
+
+    let (sender, mut receiver) = {
+        let mut scans = scans.lock().unwrap_or_else(|e| e.into_inner());
+        match scans.get(&key) {
+            // A closed channel means the first call was dropped before finishing
+            Some(running) if running.has_changed().is_ok() => (None, (**running).clone()),
+            _ => {
+                let (sender, receiver) = watch::channel(None);
+                scans.insert(key.clone(), Arc::new(receiver.clone()));
+                (Some(sender), receiver)
+            }
+        }
+    };
+
+    if sender.is_none()
+        && let Ok(projects) = receiver.wait_for(Option::is_some).await
+    {
+        return projects.clone().unwrap_or_else(|| Ok(Vec::new()));
+    }
+
+    let path = req.path.clone();
+    let skip_dirs = req.skip_dirs.clone();
+    let scan_depth = req.scan_depth;
+    let ignore_glob = req.ignore_glob.clone();
+    let force_refresh = req.action == ElixirProjectsAction::Refresh;
+    let projects = tokio::task::spawn_blocking(move || {
+        get_elixir_projects(
+            path.as_deref(),
+            skip_dirs.as_deref(),
+            scan_depth,
+            ignore_glob.as_deref(),
+            force_refresh,
+        )
+    })
+    .await
+    .unwrap_or_else(|e| Err(format!("Error: project scan failed: {}", e)));
+
+    if let Some(sender) = sender {
+        scans.lock().unwrap_or_else(|e| e.into_inner()).remove(&key);
+        let _ = sender.send(Some(projects.clone()));
+    }
+    projects
+}
+
+fn get_elixir_projects(
+    path: Option<&str>,
+    skip_dirs: Option<&[String]>,
+    scan_depth: Option<usize>,
+    ignore_glob: Option<&[String]>,
+    force_refresh: bool,
+) -> Result<Vec<PathBuf>, String> {
+    // If custom path, skip dirs, depth or globs specified, always scan (don't use cache)
+    if path.is_some() || skip_dirs.is_some() || scan_depth.is_some() || ignore_glob.is_some() {
+        return scan_elixir_projects(path, skip_dirs, scan_depth, ignore_glob);
+    }
+
+    // Try to load from cache unless force refresh
+    if !force_refresh && let Some(projects) = load_projects_from_cache() {
+        return Ok(projects);
+    }
 
     // Scan and cache
-    let projects = scan_elixir_projects(None);
+    let projects = scan_elixir_projects(None, None, None, None)?;
     let _ = save_projects_to_cache(&projects);
-    filter_ignored(projects)
+    Ok(projects)
+}
+
+/// Drop projects whose directory name is on the ignore list.
+fn filter_ignored(projects: Vec<PathBuf>) -> Vec<PathBuf> {
+    let ignored = load_ignored_projects();
+    projects
+        .into_iter()
+        .filter(|p| {
+            p.file_name()
+                .map(|n| !ignored.contains(&n.to_string_lossy().to_string()))
+                .unwrap_or(true)
+        })
+        .collect()
 }
@@ -0,0 +1,276 @@
+use rmcp::ErrorData;
+use rmcp::model::{CallToolResult, Content, Tool};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value, json};
+use std::collections::VecDeque;
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::Duration;
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct AuditLogRequest {
+    #[schemars(description = "Number of most recent entries to return (default 20, max 1000)")]
+    pub lines: Option<usize>,
+}
+
+/// Render the registered tools as a Markdown guide, with parameters and an
+/// example invocation generated from each input schema.
+pub fn handle_list_tools(mut tools: Vec<Tool>) -> CallToolResult {
+    tools.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let mut sections = vec![format!("# Steve tools ({})", tools.len())];
+    for tool in &tools {
+        let schema = tool.input_schema.as_ref();
+        let empty = Map::new();
+        let properties = schema
+            .get("properties")
+            .and_then(Value::as_object)
+            .unwrap_or(&empty);
+        let required: Vec<&str> = schema
+            .get("required")
+            .and_then(Value::as_array)
+            .map(|r| r.iter().filter_map(Value::as_str).collect())
+            .unwrap_or_default();
+
+        let mut section = format!(
+            "## {}\n\n{}",
+            tool.name,
+            tool.description.as_deref().unwrap_or("(no description)")
+        );
+        if !properties.is_empty() {
+            section.push_str("\n\n**Parameters:**\n");
+        }
+        let mut example = Map::new();
+        for (name, property) in properties {
+            // A description next to a `$ref` belongs to the field, not the type
+            let description = [property, resolve_ref(property, schema)]
+                .iter()
+                .find_map(|p| p.get("description").and_then(Value::as_str))
+                .unwrap_or_default();
+            let property = resolve_ref(property, schema);
+            let is_required = required.contains(&name.as_str());
+            let values = enum_values(property, schema);
+
+            section.push_str(&format!(
+                "\n- `{}` ({}{}){}{}",
+                name,
+                type_name(property, schema),
+                if is_required { ", required" } else { "" },
+                if description.is_empty() { "" } else { ": " },
+                description
+            ));
+            if !values.is_empty() {
+                section.push_str(&format!(" [{}]", values.join(", ")));
+            }
+            if is_required {
+                example.insert(name.clone(), example_value(property, schema, &values));
+            }
+        }
+        section.push_str(&format!("\n\n**Example:** `{}`", Value::Object(example)));
+        sections.push(section);
+    }
+
+    CallToolResult::success(vec![Content::text(sections.join("\n\n"))])
+}
+
+/// Follow a `$ref` into `$defs`, or the non-null branch of an `anyOf` used
+/// for optional fields.
+fn resolve_ref<'a>(property: &'a Value, schema: &'a Map<String, Value>) -> &'a Value {
+    if let Some(reference) = property.get("$ref").and_then(Value::as_str) {
+        let name = reference.rsplit('/').next().unwrap_or_default();
+        if let Some(def) = schema
+            .get("$defs")
+            .or_else(|| schema.get("definitions"))
+            .and_then(|defs| defs.get(name))
+        {
+            return def;
+        }
+    }
+    if let Some(variants) = property.get("anyOf").and_then(Value::as_array)
+        && let Some(variant) = variants
+            .iter()
+            .find(|v| v.get("type").and_then(Value::as_str) != Some("null"))
+    {
+        return resolve_ref(variant, schema);
+    }
+    property
+}
+
+fn type_name(property: &Value, schema: &Map<String, Value>) -> String {
+    match property.get("type") {
+        Some(Value::String(t)) if t == "array" => {
+            let items = property
+                .get("items")
+                .map(|items| type_name(resolve_ref(items, schema), schema))
+                .unwrap_or_else(|| "any".to_string());
+            format!("array of {}", items)
+        }
+        Some(Value::String(t)) => t.clone(),
+        // Optional fields are ["string", "null"]
+        Some(Value::Array(types)) => types
+            .iter()
+            .filter_map(Value::as_str)
+            .filter(|t| *t != "null")
+            .collect::<Vec<_>>()
+            .join(" | "),
+        _ if !enum_values(property, schema).is_empty() => "string".to_string(),
+        _ => "any".to_string(),
+    }
+}
+
+/// Allowed values of a string enum, from `enum` or a `oneOf` of `const`s.
+fn enum_values(property: &Value, schema: &Map<String, Value>) -> Vec<String> {
+    let property = resolve_ref(property, schema);
+    if let Some(values) = property.get("enum").and_then(Value::as_array) {
+        return values
+            .iter()
+            .filter_map(Value::as_str)
+            .map(str::to_string)
+            .collect();
+    }
+    property
+        .get("oneOf")
+        .and_then(Value::as_array)
+        .map(|variants| {
+            variants
+                .iter()
+                .filter_map(|v| v.get("const").and_then(Value::as_str))
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn example_value(property: &Value, schema: &Map<String, Value>, values: &[String]) -> Value {
+    if let Some(first) = values.first() {
+        return json!(first);
+    }
+    match type_name(property, schema).as_str() {
+        "integer" => json!(1),
+        "number" => json!(1.0),
+        "boolean" => json!(true),
+        "object" => json!({}),
+        t if t.starts_with("array") => json!([]),
+        _ => json!("..."),
+    }
+}
+
+/// The error a tool call ended with, if any. Most tools report failures as
+/// text starting with "✗" or "Error" rather than as MCP errors, so those count
+/// too.
+pub fn call_error(result: &Result<CallToolResult, ErrorData>) -> Option<String> {
+    let result = match result {
+        Ok(result) => result,
+        Err(e) => return Some(e.message.to_string()),
+    };
+    let text: Vec<&str> = result
+        .content
+        .iter()
+        .filter_map(|c| c.as_text().map(|t| t.text.as_str()))
+        .collect();
+    let text = text.join("\n");
+
+    let failed = result.is_error == Some(true)
+        || text.starts_with('✗')
+        || text.starts_with("Error")
+        || text.starts_with("Unknown");
+    failed.then_some(text)
+}
+
+/// Return the last failed tool call and forget it.
+pub fn handle_last_error(last_error: &Mutex<Option<(String, String, String)>>) -> CallToolResult {
+    let taken = last_error.lock().unwrap_or_else(|e| e.into_inner()).take();
+    let text = match taken {
+        Some((tool, request, error)) => {
+            format!("Tool: {}\nRequest: {}\nError: {}", tool, request, error)
+        }
+        None => "No failed tool calls since the last check".to_string(),
+    };
+    CallToolResult::success(vec![Content::text(text)])
+}
+
+/// Open `~/.cache/steve/audit.log` for appending, or `None` when it cannot be
+/// created.
+pub fn open_audit_log() -> Option<BufWriter<File>> {
+    let path = get_audit_log_path();
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).ok()?;
+    }
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .ok()
+        .map(BufWriter::new)
+}
+
+/// Request fields that may carry secrets or private text; their values are
+/// replaced before the request is written to the audit log.
+const REDACTED_FIELDS: [&str; 6] = ["token", "headers", "body", "content", "text", "env"];
+
+/// Append one JSON line describing a tool call.
+pub fn write_audit_entry(
+    log: &Mutex<Option<BufWriter<File>>>,
+    tool: &str,
+    request: Option<&Map<String, Value>>,
+    duration: Duration,
+    success: bool,
+) {
+    let request = request.map(|args| {
+        let mut args = args.clone();
+        for field in REDACTED_FIELDS {
+            if let Some(value) = args.get_mut(field) {
+                *value = json!("[redacted]");
+            }
+        }
+        args
+    });
+    let entry = json!({
+        "timestamp": chrono::Local::now().to_rfc3339(),
+        "tool": tool,
+        "request": request,
+        "duration_ms": duration.as_millis() as u64,
+        "success": success,
+    });
+
+    let mut log = log.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(writer) = log.as_mut() {
+        // Flush every entry so audit_log and `tail -f` see it immediately
+        let _ = writeln!(writer, "{}", entry).and_then(|_| writer.flush());
+    }
+}
+
+pub fn handle_audit_log(req: AuditLogRequest) -> CallToolResult {
+    let count = req.lines.unwrap_or(20).clamp(1, 1000);
+    let path = get_audit_log_path();
+
+    let Ok(file) = File::open(&path) else {
+        return CallToolResult::success(vec![Content::text(format!(
+            "No audit log at {}",
+            path.display()
+        ))]);
+    };
+    let mut entries: VecDeque<String> = VecDeque::with_capacity(count);
+    for line in BufReader::new(file).lines().map_while(Result::ok) {
+        if entries.len() == count {
+            entries.pop_front();
+        }
+        entries.push_back(line);
+    }
+
+    let text = if entries.is_empty() {
+        "Audit log is empty".to_string()
+    } else {
+        Vec::from(entries).join("\n")
+    };
+    CallToolResult::success(vec![Content::text(text)])
+}
+
+fn get_audit_log_path() -> PathBuf {
+    dirs::home_dir()
+        .map(|h| h.join(".cache/steve/audit.log"))
+        .unwrap_or_else(|| PathBuf::from("audit.log"))
+}
@@ -1,5 +1,19 @@
+pub mod desktop;
+pub mod docker;
 pub mod elixir_projects;
+pub mod meta;
+pub mod network;
+pub mod notes;
 pub mod spotify;
+pub mod system;
+pub mod util;
 
+pub use desktop::*;
+pub use docker::*;
 pub use elixir_projects::*;
+pub use meta::*;
+pub use network::*;
+pub use notes::*;
 pub use spotify::*;
+pub use system::*;
+pub use util::*;
@@ -0,0 +1,404 @@
+use rmcp::model::{CallToolResult, Content};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::io::ErrorKind;
+use std::net::Ipv4Addr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::net::TcpStream;
+use tokio::process::Command;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+use tokio::time::timeout;
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SshTestRequest {
+    #[schemars(description = "Host name or IP address to check")]
+    pub host: String,
+    #[schemars(description = "SSH port (defaults to 22)")]
+    pub port: Option<u16>,
+    #[schemars(description = "User name, only used for display")]
+    pub user: Option<String>,
+    #[schemars(description = "Connection timeout in seconds (defaults to 5)")]
+    pub timeout_secs: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct PortCheckRequest {
+    #[schemars(
+        description = "Host name, IP address, or IPv4 CIDR range up to /24 (e.g. 192.168.1.0/24)"
+    )]
+    pub host: String,
+    #[schemars(description = "TCP ports to check (at most 4096 host/port checks in total)")]
+    pub ports: Vec<u16>,
+    #[schemars(description = "Per-connection timeout in milliseconds (defaults to 1000)")]
+    pub timeout_ms: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct HttpRequest {
+    #[schemars(description = "HTTP method: GET, POST, PUT, PATCH, or DELETE")]
+    pub method: String,
+    #[schemars(description = "Request URL")]
+    pub url: String,
+    #[schemars(description = "Request body")]
+    pub body: Option<String>,
+    #[schemars(
+        description = "Content-Type of the body (defaults to application/json when a body is given)"
+    )]
+    pub content_type: Option<String>,
+    #[schemars(description = "Additional request headers")]
+    pub headers: Option<HashMap<String, String>>,
+    #[schemars(description = "Request timeout in seconds (defaults to 30)")]
+    pub timeout_secs: Option<u64>,
+}
+
+pub async fn handle_ssh_test(req: SshTestRequest) -> CallToolResult {
+    let port = req.port.unwrap_or(22);
+    let limit = Duration::from_secs(req.timeout_secs.unwrap_or(5));
+    let target = match req.user {
+        Some(ref user) => format!("{}@{}:{}", user, req.host, port),
+        None => format!("{}:{}", req.host, port),
+    };
+
+    let started = Instant::now();
+    let stream = match timeout(limit, TcpStream::connect((req.host.as_str(), port))).await {
+        Ok(Ok(stream)) => stream,
+        Ok(Err(e)) => {
+            return CallToolResult::success(vec![Content::text(format!(
+                "✗ {} unreachable: {}",
+                target, e
+            ))]);
+        }
+        Err(_) => {
+            return CallToolResult::success(vec![Content::text(format!(
+                "✗ {} timed out after {}s",
+                target,
+                limit.as_secs()
+            ))]);
+        }
+    };
+    let latency = started.elapsed();
+
+    // The server sends its identification string first, e.g. "SSH-2.0-OpenSSH_9.6"
+    let mut banner = String::new();
+    let mut reader = BufReader::new(stream);
+    let banner = match timeout(limit, reader.read_line(&mut banner)).await {
+        Ok(Ok(_)) if banner.starts_with("SSH-") => banner.trim().to_string(),
+        Ok(Ok(_)) => "no SSH banner (not an SSH server?)".to_string(),
+        _ => "no banner received".to_string(),
+    };
+
+    let known_host = is_known_host(&req.host, port).await;
+
+    CallToolResult::success(vec![Content::text(format!(
+        "✓ {} reachable in {} ms\nServer: {}\nIn known_hosts: {}",
+        target,
+        latency.as_millis(),
+        banner,
+        if known_host { "yes" } else { "no" }
+    ))])
+}
+
+/// Check `~/.ssh/known_hosts` via `ssh-keygen -F`, which also handles hashed entries.
+async fn is_known_host(host: &str, port: u16) -> bool {
+    let Some(known_hosts) = dirs::home_dir().map(|h| h.join(".ssh/known_hosts")) else {
+        return false;
+    };
+    let lookup = if port == 22 {
+        host.to_string()
+    } else {
+        format!("[{}]:{}", host, port)
+    };
+
+    Command::new("ssh-keygen")
+        .arg("-F")
+        .arg(&lookup)
+        .arg("-f")
+        .arg(&known_hosts)
+        .output()
+        .await
+        .map(|o| o.status.success() && !o.stdout.is_empty())
+        .unwrap_or(false)
+}
+
+pub async fn handle_ssh_list_keys() -> CallToolResult {
+    let Some(ssh_dir) = dirs::home_dir().map(|h| h.join(".ssh")) else {
+        return CallToolResult::success(vec![Content::text(
+            "Could not determine home directory".to_string(),
+        )]);
+    };
+
+    let mut pub_keys: Vec<_> = fs::read_dir(&ssh_dir)
+        .map(|entries| {
+            entries
+                .filter_map(|e| e.ok())
+                .map(|e| e.path())
+                .filter(|p| p.extension().is_some_and(|ext| ext == "pub"))
+                .collect()
+        })
+        .unwrap_or_default();
+    pub_keys.sort();
+
+    let mut key_lines: Vec<String> = Vec::new();
+    for key in &pub_keys {
+        let name = key
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let line = match Command::new("ssh-keygen")
+            .arg("-l")
+            .arg("-f")
+            .arg(key)
+            .output()
+            .await
+        {
+            Ok(o) if o.status.success() => {
+                format!(
+                    "{}: {}",
+                    name,
+                    format_fingerprint(&String::from_utf8_lossy(&o.stdout))
+                )
+            }
+            Ok(o) => format!("{}: ✗ {}", name, String::from_utf8_lossy(&o.stderr).trim()),
+            Err(e) => format!("{}: ✗ Failed to run ssh-keygen: {}", name, e),
+        };
+        key_lines.push(line);
+    }
+
+    let agent_lines: Vec<String> = match Command::new("ssh-add").arg("-l").output().await {
+        Ok(o) if o.status.success() => String::from_utf8_lossy(&o.stdout)
+            .lines()
+            .map(format_fingerprint)
+            .collect(),
+        // Exit code 1 means the agent has no identities, 2 means no agent is running
+        Ok(o) => vec![format!(
+            "{}{}",
+            String::from_utf8_lossy(&o.stdout).trim(),
+            String::from_utf8_lossy(&o.stderr).trim()
+        )],
+        Err(e) => vec![format!("Failed to run ssh-add: {}", e)],
+    };
+
+    let mut output = if key_lines.is_empty() {
+        format!("No public keys found in {}", ssh_dir.display())
+    } else {
+        format!(
+            "Keys in {} ({}):\n  {}",
+            ssh_dir.display(),
+            key_lines.len(),
+            key_lines.join("\n  ")
+        )
+    };
+    output.push_str(&format!("\n\nSSH agent:\n  {}", agent_lines.join("\n  ")));
+
+    CallToolResult::success(vec![Content::text(output)])
+}
+
+/// Reformat `256 SHA256:abc user@host (ED25519)` as `ED25519 256 SHA256:abc user@host`.
+fn format_fingerprint(line: &str) -> String {
+    let line = line.trim();
+    let (rest, algorithm) = match line.rsplit_once(" (") {
+        Some((rest, algorithm)) => (rest, algorithm.trim_end_matches(')')),
+        None => (line, "?"),
+    };
+    let mut parts = rest.splitn(3, ' ');
+    let bits = parts.next().unwrap_or("?");
+    let fingerprint = parts.next().unwrap_or("?");
+    let comment = parts.next().unwrap_or("");
+    format!("{} {} {} {}", algorithm, bits, fingerprint, comment)
+        .trim_end()
+        .to_string()
+}
+
+const MAX_PORT_CHECKS: usize = 4096;
+const MAX_CONCURRENT_CONNECTS: usize = 100;
+
+/// Outcome of one TCP connect. Refused and timed out connections count as
+/// closed; anything else (unreachable network, DNS failure, ...) is reported
+/// as an error.
+enum PortState {
+    Open(Duration),
+    Closed,
+    Failed(String),
+}
+
+pub async fn handle_port_check(req: PortCheckRequest) -> CallToolResult {
+    if req.ports.is_empty() {
+        return CallToolResult::success(vec![Content::text(
+            "Error: at least one port is required".to_string(),
+        )]);
+    }
+
+    let hosts = match expand_hosts(&req.host) {
+        Ok(hosts) => hosts,
+        Err(e) => return CallToolResult::success(vec![Content::text(e)]),
+    };
+    let checks_total = hosts.len() * req.ports.len();
+    if checks_total > MAX_PORT_CHECKS {
+        return CallToolResult::success(vec![Content::text(format!(
+            "Error: {} host/port checks requested; the limit is {}",
+            checks_total, MAX_PORT_CHECKS
+        ))]);
+    }
+    let limit = Duration::from_millis(req.timeout_ms.unwrap_or(1000));
+
+    // Keep well below the usual 1024 open file limit, where connects would
+    // start failing with EMFILE
+    let permits = Arc::new(Semaphore::new(MAX_CONCURRENT_CONNECTS));
+    let mut checks = JoinSet::new();
+    for host in &hosts {
+        for &port in &req.ports {
+            let host = host.clone();
+            let permits = permits.clone();
+            checks.spawn(async move {
+                let _permit = permits.acquire_owned().await;
+                let started = Instant::now();
+                let state = match timeout(limit, TcpStream::connect((host.as_str(), port))).await {
+                    Ok(Ok(_)) => PortState::Open(started.elapsed()),
+                    Ok(Err(e)) if e.kind() == ErrorKind::ConnectionRefused => PortState::Closed,
+                    Err(_) => PortState::Closed,
+                    Ok(Err(e)) => PortState::Failed(e.to_string()),
+                };
+                (port, host, state)
+            });
+        }
+    }
+
+    let mut results = checks.join_all().await;
+    results.sort_by(|a, b| (a.0, &a.1).cmp(&(b.0, &b.1)));
+
+    // For range scans only the open ports and failed checks are interesting
+    let is_range = hosts.len() > 1;
+    let lines: Vec<String> = results
+        .iter()
+        .filter(|(_, _, state)| !is_range || !matches!(state, PortState::Closed))
+        .map(|(port, host, state)| match state {
+            PortState::Open(elapsed) => {
+                format!("✓ {}:{} open ({} ms)", host, port, elapsed.as_millis())
+            }
+            PortState::Closed => format!("✗ {}:{} closed", host, port),
+            PortState::Failed(e) => format!("✗ {}:{} error: {}", host, port, e),
+        })
+        .collect();
+
+    let open_count = results
+        .iter()
+        .filter(|r| matches!(r.2, PortState::Open(_)))
+        .count();
+    CallToolResult::success(vec![Content::text(format!(
+        "{} of {} checks open:\n{}",
+        open_count,
+        results.len(),
+        lines.join("\n")
+    ))])
+}
+
+/// Expand an IPv4 CIDR range (no larger than /24) into host addresses, or
+/// return the host unchanged.
+fn expand_hosts(host: &str) -> Result<Vec<String>, String> {
+    let Some((addr, prefix)) = host.split_once('/') else {
+        return Ok(vec![host.to_string()]);
+    };
+
+    let addr: Ipv4Addr = addr
+        .parse()
+        .map_err(|_| format!("Invalid IPv4 address in '{}'", host))?;
+    let prefix: u32 = prefix
+        .parse()
+        .map_err(|_| format!("Invalid prefix length in '{}'", host))?;
+    if !(24..=32).contains(&prefix) {
+        return Err(format!(
+            "CIDR range '{}' is too large; use /24 or smaller",
+            host
+        ));
+    }
+
+    let size = 1u32 << (32 - prefix);
+    let network = u32::from(addr) & !(size - 1);
+    Ok((0..size)
+        .map(|offset| Ipv4Addr::from(network + offset).to_string())
+        .collect())
+}
+
+pub async fn handle_http(req: HttpRequest) -> CallToolResult {
+    const MAX_BODY: usize = 8 * 1024;
+
+    let method = match req.method.to_uppercase().as_str() {
+        "GET" => reqwest::Method::GET,
+        "POST" => reqwest::Method::POST,
+        "PUT" => reqwest::Method::PUT,
+        "PATCH" => reqwest::Method::PATCH,
+        "DELETE" => reqwest::Method::DELETE,
+        _ => {
+            return CallToolResult::success(vec![Content::text(format!(
+                "Unknown method '{}'. Use: GET, POST, PUT, PATCH, DELETE",
+                req.method
+            ))]);
+        }
+    };
+
+    let client = match reqwest::Client::builder()
+        .timeout(Duration::from_secs(req.timeout_secs.unwrap_or(30)))
+        .build()
+    {
+        Ok(client) => client,
+        Err(e) => {
+            return CallToolResult::success(vec![Content::text(format!(
+                "✗ Failed to create HTTP client: {}",
+                e
+            ))]);
+        }
+    };
+
+    let mut request = client.request(method.clone(), &req.url);
+    for (name, value) in req.headers.iter().flatten() {
+        request = request.header(name, value);
+    }
+    if let Some(ref body) = req.body {
+        let content_type = req.content_type.as_deref().unwrap_or("application/json");
+        request = request
+            .header(reqwest::header::CONTENT_TYPE, content_type)
+            .body(body.clone());
+    }
+
+    let started = Instant::now();
+    let response = match request.send().await {
+        Ok(response) => response,
+        Err(e) => {
+            return CallToolResult::success(vec![Content::text(format!(
+                "✗ {} {} failed: {}",
+                method, req.url, e
+            ))]);
+        }
+    };
+    let elapsed = started.elapsed();
+
+    let status = response.status();
+    let headers: Vec<String> = response
+        .headers()
+        .iter()
+        .map(|(name, value)| format!("{}: {}", name, value.to_str().unwrap_or("<binary>")))
+        .collect();
+    let body = response.bytes().await.unwrap_or_default();
+    let shown = String::from_utf8_lossy(&body[..body.len().min(MAX_BODY)]);
+    let truncated = if body.len() > MAX_BODY {
+        format!("\n... ({} of {} bytes shown)", MAX_BODY, body.len())
+    } else {
+        String::new()
+    };
+
+    CallToolResult::success(vec![Content::text(format!(
+        "{} {} -> {} ({} ms)\n\n{}\n\n{}{}",
+        method,
+        req.url,
+        status,
+        elapsed.as_millis(),
+        headers.join("\n"),
+        shown,
+        truncated
+    ))])
+}
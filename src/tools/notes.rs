@@ -0,0 +1,483 @@
+use chrono::{DateTime, Local};
+use rmcp::model::{CallToolResult, Content};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+
+use super::desktop::xdg_open;
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct NoteRequest {
+    #[schemars(description = "Action to perform: add, list, get, search, delete")]
+    pub action: String,
+    #[schemars(description = "Note title (required for add, get, delete)")]
+    pub title: Option<String>,
+    #[schemars(description = "Markdown content (required for add)")]
+    pub content: Option<String>,
+    #[schemars(description = "Text to look for in all notes (search)")]
+    pub search: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct TodoRequest {
+    #[schemars(description = "Action to perform: add, list, done, delete, clear")]
+    pub action: String,
+    #[schemars(description = "Todo text (required for add)")]
+    pub item: Option<String>,
+    #[schemars(description = "Todo id (required for done and delete)")]
+    pub id: Option<usize>,
+    #[schemars(description = "Tags to attach (add) or to filter by (list)")]
+    pub tags: Option<Vec<String>>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Todo {
+    id: usize,
+    item: String,
+    #[serde(default)]
+    tags: Vec<String>,
+    #[serde(default)]
+    done: bool,
+    created_at: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct BookmarkRequest {
+    #[schemars(description = "Action to perform: add, list, search, delete, open")]
+    pub action: String,
+    #[schemars(description = "Bookmark URL (required for add and delete; open uses it directly)")]
+    pub url: Option<String>,
+    #[schemars(description = "Bookmark title (add)")]
+    pub title: Option<String>,
+    #[schemars(description = "Tags to attach (add)")]
+    pub tags: Option<Vec<String>>,
+    #[schemars(
+        description = "Text to match against url, title or tags (search; open picks the newest match)"
+    )]
+    pub search: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Bookmark {
+    url: String,
+    #[serde(default)]
+    title: Option<String>,
+    #[serde(default)]
+    tags: Vec<String>,
+    added_at: String,
+}
+
+pub fn handle_note(req: NoteRequest) -> CallToolResult {
+    let dir = get_notes_dir();
+    let title = req
+        .title
+        .as_deref()
+        .map(str::trim)
+        .filter(|t| !t.is_empty());
+
+    let result = match (req.action.as_str(), title) {
+        ("add", Some(title)) => match req.content {
+            Some(ref content) => fs::create_dir_all(&dir)
+                .and_then(|_| {
+                    fs::write(
+                        note_path(title),
+                        format!("# {}\n\n{}\n", title, content.trim_end()),
+                    )
+                })
+                .map(|_| format!("✓ Saved note '{}'", title))
+                .map_err(|e| format!("Failed to save note: {}", e)),
+            None => Err("Error: 'content' is required for add action".to_string()),
+        },
+        ("list", _) => Ok(list_notes()),
+        ("get", Some(title)) => {
+            fs::read_to_string(note_path(title)).map_err(|_| format!("No note titled '{}'", title))
+        }
+        ("search", _) => match req.search {
+            Some(ref search) => Ok(search_notes(search)),
+            None => Err("Error: 'search' is required for search action".to_string()),
+        },
+        ("delete", Some(title)) => fs::remove_file(note_path(title))
+            .map(|_| format!("✓ Deleted note '{}'", title))
+            .map_err(|_| format!("No note titled '{}'", title)),
+        ("add" | "get" | "delete", None) => Err(format!(
+            "Error: 'title' is required for {} action",
+            req.action
+        )),
+        _ => Err(format!(
+            "Unknown action '{}'. Use: add, list, get, search, delete",
+            req.action
+        )),
+    };
+
+    match result {
+        Ok(output) => CallToolResult::success(vec![Content::text(output)]),
+        Err(e) => CallToolResult::success(vec![Content::text(e)]),
+    }
+}
+
+pub fn handle_todo(req: TodoRequest) -> CallToolResult {
+    // Saving over a file that failed to parse would lose every todo in it
+    let mut todos = match load_todos() {
+        Ok(todos) => todos,
+        Err(e) => return CallToolResult::success(vec![Content::text(e)]),
+    };
+    let tags = req.tags.clone().unwrap_or_default();
+
+    let result = match req.action.as_str() {
+        "add" => match req.item.as_deref().map(str::trim).filter(|i| !i.is_empty()) {
+            Some(item) => {
+                let id = todos.iter().map(|t| t.id).max().unwrap_or(0) + 1;
+                todos.push(Todo {
+                    id,
+                    item: item.to_string(),
+                    tags,
+                    done: false,
+                    created_at: Local::now().to_rfc3339(),
+                });
+                save_todos(&todos)
+            }
+            None => Err("Error: 'item' is required for add action".to_string()),
+        },
+        "list" => {
+            let filtered: Vec<&Todo> = todos
+                .iter()
+                .filter(|t| tags.is_empty() || t.tags.iter().any(|tag| tags.contains(tag)))
+                .collect();
+            return CallToolResult::success(vec![Content::text(format_todos(&filtered))]);
+        }
+        "done" | "delete" => match req.id {
+            Some(id) => match todos.iter().position(|t| t.id == id) {
+                Some(index) => {
+                    if req.action == "done" {
+                        todos[index].done = true;
+                    } else {
+                        todos.remove(index);
+                    }
+                    save_todos(&todos)
+                }
+                None => Err(format!("No todo with id {}", id)),
+            },
+            None => Err(format!("Error: 'id' is required for {} action", req.action)),
+        },
+        "clear" => {
+            todos.retain(|t| !t.done);
+            save_todos(&todos)
+        }
+        _ => Err(format!(
+            "Unknown action '{}'. Use: add, list, done, delete, clear",
+            req.action
+        )),
+    };
+
+    match result {
+        Ok(()) => CallToolResult::success(vec![Content::text(format_todos(
+            &todos.iter().collect::<Vec<_>>(),
+        ))]),
+        Err(e) => CallToolResult::success(vec![Content::text(e)]),
+    }
+}
+
+fn format_todos(todos: &[&Todo]) -> String {
+    if todos.is_empty() {
+        return "No todos".to_string();
+    }
+    todos
+        .iter()
+        .map(|t| {
+            let tags: String = t.tags.iter().map(|tag| format!(" #{}", tag)).collect();
+            format!(
+                "[{}] {}. {}{} ({})",
+                if t.done { "x" } else { " " },
+                t.id,
+                t.item,
+                tags,
+                t.created_at.get(..10).unwrap_or(&t.created_at)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// The saved todos; a missing file is an empty list, an unreadable one an error.
+fn load_todos() -> Result<Vec<Todo>, String> {
+    let path = get_todos_path();
+    let content = match fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(format!("✗ Failed to read {}: {}", path.display(), e)),
+    };
+    serde_json::from_str(&content).map_err(|e| {
+        format!(
+            "✗ Failed to parse {}: {} (fix or move the file; it was left unchanged)",
+            path.display(),
+            e
+        )
+    })
+}
+
+fn save_todos(todos: &[Todo]) -> Result<(), String> {
+    let path = get_todos_path();
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to save todos: {}", e))?;
+    }
+    let json =
+        serde_json::to_string_pretty(todos).map_err(|e| format!("Failed to save todos: {}", e))?;
+    fs::write(&path, json).map_err(|e| format!("Failed to save todos: {}", e))
+}
+
+pub fn handle_bookmark(req: BookmarkRequest) -> CallToolResult {
+    let mut bookmarks = load_bookmarks();
+    // Newest first
+    bookmarks.reverse();
+    let url = req.url.as_deref().map(str::trim).filter(|u| !u.is_empty());
+
+    let result = match (req.action.as_str(), url) {
+        ("add", Some(url)) => {
+            if bookmarks.iter().any(|b| b.url == url) {
+                Err(format!("Already bookmarked: {}", url))
+            } else {
+                append_bookmark(&Bookmark {
+                    url: url.to_string(),
+                    title: req.title.clone(),
+                    tags: req.tags.clone().unwrap_or_default(),
+                    added_at: Local::now().to_rfc3339(),
+                })
+                .map(|_| format!("✓ Bookmarked {}", url))
+            }
+        }
+        ("list", _) => Ok(format_bookmarks(bookmarks.iter())),
+        ("search", _) => match req.search {
+            Some(ref search) => Ok(format_bookmarks(
+                bookmarks.iter().filter(|b| bookmark_matches(b, search)),
+            )),
+            None => Err("Error: 'search' is required for search action".to_string()),
+        },
+        ("delete", Some(url)) => match delete_bookmark(url) {
+            Ok(true) => Ok(format!("✓ Deleted bookmark {}", url)),
+            Ok(false) => Err(format!("No bookmark for {}", url)),
+            Err(e) => Err(e),
+        },
+        ("open", Some(url)) => xdg_open(url).map(|_| format!("✓ Opened {}", url)),
+        ("open", None) => match req.search {
+            Some(ref search) => match bookmarks.iter().find(|b| bookmark_matches(b, search)) {
+                Some(bookmark) => {
+                    xdg_open(&bookmark.url).map(|_| format!("✓ Opened {}", bookmark.url))
+                }
+                None => Err(format!("No bookmark matching '{}'", search)),
+            },
+            None => Err("Error: 'url' or 'search' is required for open action".to_string()),
+        },
+        ("add" | "delete", None) => Err(format!(
+            "Error: 'url' is required for {} action",
+            req.action
+        )),
+        _ => Err(format!(
+            "Unknown action '{}'. Use: add, list, search, delete, open",
+            req.action
+        )),
+    };
+
+    match result {
+        Ok(output) => CallToolResult::success(vec![Content::text(output)]),
+        Err(e) => CallToolResult::success(vec![Content::text(e)]),
+    }
+}
+
+fn bookmark_matches(bookmark: &Bookmark, search: &str) -> bool {
+    let search = search.to_lowercase();
+    bookmark.url.to_lowercase().contains(&search)
+        || bookmark
+            .title
+            .as_deref()
+            .is_some_and(|t| t.to_lowercase().contains(&search))
+        || bookmark.tags.iter().any(|t| t.to_lowercase() == search)
+}
+
+fn format_bookmarks<'a>(bookmarks: impl Iterator<Item = &'a Bookmark>) -> String {
+    let lines: Vec<String> = bookmarks
+        .map(|b| {
+            let tags: String = b.tags.iter().map(|tag| format!(" #{}", tag)).collect();
+            format!(
+                "{} - {}{} ({})",
+                b.title.as_deref().unwrap_or("(untitled)"),
+                b.url,
+                tags,
+                b.added_at.get(..10).unwrap_or(&b.added_at)
+            )
+        })
+        .collect();
+    if lines.is_empty() {
+        "No bookmarks found".to_string()
+    } else {
+        lines.join("\n")
+    }
+}
+
+/// Bookmarks in the order they were added, skipping unreadable lines.
+fn load_bookmarks() -> Vec<Bookmark> {
+    fs::read_to_string(get_bookmarks_path())
+        .map(|content| {
+            content
+                .lines()
+                .filter_map(|line| serde_json::from_str(line).ok())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn append_bookmark(bookmark: &Bookmark) -> Result<(), String> {
+    let path = get_bookmarks_path();
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to save bookmark: {}", e))?;
+    }
+    let line =
+        serde_json::to_string(bookmark).map_err(|e| format!("Failed to save bookmark: {}", e))?;
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .and_then(|mut file| writeln!(file, "{}", line))
+        .map_err(|e| format!("Failed to save bookmark: {}", e))
+}
+
+/// Remove the bookmark for `url`, returning whether there was one. Other
+/// lines are written back exactly as they were, including ones that
+/// `load_bookmarks` cannot read.
+fn delete_bookmark(url: &str) -> Result<bool, String> {
+    let path = get_bookmarks_path();
+    let content =
+        fs::read_to_string(&path).map_err(|e| format!("Failed to read bookmarks: {}", e))?;
+    let is_target = |line: &str| {
+        serde_json::from_str::<Bookmark>(line).is_ok_and(|bookmark| bookmark.url == url)
+    };
+    if !content.lines().any(is_target) {
+        return Ok(false);
+    }
+
+    let kept: String = content
+        .lines()
+        .filter(|line| !is_target(line))
+        .map(|line| format!("{}\n", line))
+        .collect();
+    fs::write(&path, kept).map_err(|e| format!("Failed to save bookmarks: {}", e))?;
+    Ok(true)
+}
+
+/// Note titles with their last-modified time, newest first.
+fn list_notes() -> String {
+    let mut notes: Vec<(DateTime<Local>, String)> = note_files()
+        .into_iter()
+        .filter_map(|path| {
+            let modified = fs::metadata(&path).and_then(|m| m.modified()).ok()?;
+            Some((DateTime::from(modified), note_title(&path)))
+        })
+        .collect();
+    notes.sort_by_key(|(modified, _)| std::cmp::Reverse(*modified));
+
+    if notes.is_empty() {
+        return "No notes found".to_string();
+    }
+    notes
+        .iter()
+        .map(|(modified, title)| format!("{} ({})", title, modified.format("%Y-%m-%d %H:%M")))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Lines containing `search` (case-insensitive), prefixed with the note title.
+fn search_notes(search: &str) -> String {
+    let search = search.to_lowercase();
+    let mut matches = Vec::new();
+    for path in note_files() {
+        let Ok(content) = fs::read_to_string(&path) else {
+            continue;
+        };
+        let title = note_title(&path);
+        for (line_number, line) in content.lines().enumerate() {
+            if line.to_lowercase().contains(&search) {
+                matches.push(format!("{}:{}: {}", title, line_number + 1, line.trim()));
+            }
+        }
+    }
+
+    if matches.is_empty() {
+        "No matching notes found".to_string()
+    } else {
+        matches.join("\n")
+    }
+}
+
+fn note_files() -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = fs::read_dir(get_notes_dir())
+        .map(|entries| {
+            entries
+                .filter_map(|e| e.ok())
+                .map(|e| e.path())
+                .filter(|p| p.extension().is_some_and(|ext| ext == "md"))
+                .collect()
+        })
+        .unwrap_or_default();
+    files.sort();
+    files
+}
+
+/// The `# Title` heading written by add, falling back to the file name.
+fn note_title(path: &Path) -> String {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|content| {
+            content
+                .lines()
+                .next()?
+                .strip_prefix("# ")
+                .map(|t| t.trim().to_string())
+        })
+        .unwrap_or_else(|| {
+            path.file_stem()
+                .map(|s| s.to_string_lossy().to_string())
+                .unwrap_or_default()
+        })
+}
+
+fn note_path(title: &str) -> PathBuf {
+    get_notes_dir().join(format!("{}.md", slugify(title)))
+}
+
+/// Lowercase ASCII letters and digits, with every other run of characters
+/// replaced by a single `-`.
+fn slugify(title: &str) -> String {
+    let mut slug = String::new();
+    for c in title.chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c.to_ascii_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    let slug = slug.trim_end_matches('-');
+    if slug.is_empty() {
+        "untitled".to_string()
+    } else {
+        slug.to_string()
+    }
+}
+
+fn get_notes_dir() -> PathBuf {
+    dirs::home_dir()
+        .map(|h| h.join(".local/share/steve/notes"))
+        .unwrap_or_else(|| PathBuf::from("notes"))
+}
+
+fn get_todos_path() -> PathBuf {
+    dirs::home_dir()
+        .map(|h| h.join(".local/share/steve/todos.json"))
+        .unwrap_or_else(|| PathBuf::from("todos.json"))
+}
+
+fn get_bookmarks_path() -> PathBuf {
+    dirs::home_dir()
+        .map(|h| h.join(".local/share/steve/bookmarks.jsonl"))
+        .unwrap_or_else(|| PathBuf::from("bookmarks.jsonl"))
+}
@@ -3,24 +3,43 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use tokio::process::Command;
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SpotifyAction {
+    Play,
+    Pause,
+    PlayPause,
+    Next,
+    Previous,
+    Status,
+    VolumeUp,
+    VolumeDown,
+    VolumeSet,
+    VolumeGet,
+    Seek,
+    ListPlayers,
+}
+
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct SpotifyRequest {
+    #[schemars(description = "Action to perform")]
+    pub action: SpotifyAction,
     #[schemars(
-        description = "Action to perform: play, pause, play_pause, next, previous, or status"
+        description = "Volume from 0.0 to 1.0 for volume_set, or seconds to seek for seek (negative rewinds)"
     )]
-    pub action: String,
+    pub value: Option<f64>,
 }
 
 pub async fn handle_spotify(req: SpotifyRequest) -> CallToolResult {
     let player = "firefox";
 
-    let result = match req.action.as_str() {
-        "play" => run_playerctl(player, &["play"]).await,
-        "pause" => run_playerctl(player, &["pause"]).await,
-        "play_pause" => run_playerctl(player, &["play-pause"]).await,
-        "next" => run_playerctl(player, &["next"]).await,
-        "previous" => run_playerctl(player, &["previous"]).await,
-        "status" => {
+    let result = match req.action {
+        SpotifyAction::Play => run_playerctl(player, &["play"]).await,
+        SpotifyAction::Pause => run_playerctl(player, &["pause"]).await,
+        SpotifyAction::PlayPause => run_playerctl(player, &["play-pause"]).await,
+        SpotifyAction::Next => run_playerctl(player, &["next"]).await,
+        SpotifyAction::Previous => run_playerctl(player, &["previous"]).await,
+        SpotifyAction::Status => {
             let status = run_playerctl(player, &["status"]).await.unwrap_or_default();
             let metadata = run_playerctl(
                 player,
@@ -30,10 +49,37 @@ pub async fn handle_spotify(req: SpotifyRequest) -> CallToolResult {
             .unwrap_or_default();
             Ok(format!("{}\n{}", status.trim(), metadata.trim()))
         }
-        _ => Err(format!(
-            "Unknown action '{}'. Use: play, pause, play_pause, next, previous, status",
-            req.action
-        )),
+        SpotifyAction::VolumeUp => change_volume(player, "0.1+").await,
+        SpotifyAction::VolumeDown => change_volume(player, "0.1-").await,
+        SpotifyAction::VolumeSet => match req.value {
+            Some(volume) => change_volume(player, &volume.clamp(0.0, 1.0).to_string()).await,
+            None => Err("Error: 'value' is required for volume_set action".to_string()),
+        },
+        SpotifyAction::VolumeGet => run_playerctl(player, &["volume"])
+            .await
+            .map(|volume| format_volume(&volume)),
+        SpotifyAction::Seek => match req.value {
+            // playerctl takes relative offsets as "10+" or "10-"
+            Some(seconds) => {
+                let offset = format!("{}{}", seconds.abs(), if seconds < 0.0 { "-" } else { "+" });
+                run_playerctl(player, &["position", &offset])
+                    .await
+                    .map(|_| format!("Seeked {}s", seconds))
+            }
+            None => Err("Error: 'value' is required for seek action".to_string()),
+        },
+        SpotifyAction::ListPlayers => {
+            match Command::new("playerctl").arg("--list-all").output().await {
+                Ok(output) if output.status.success() => {
+                    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+                }
+                Ok(output) => Err(format!(
+                    "playerctl error: {}",
+                    String::from_utf8_lossy(&output.stderr).trim()
+                )),
+                Err(e) => Err(format!("Failed to run playerctl: {}", e)),
+            }
+        }
     };
 
     match result {
@@ -58,3 +104,18 @@ async fn run_playerctl(player: &str, args: &[&str]) -> Result<String, String> {
         Err(e) => Err(format!("Failed to run playerctl: {}", e)),
     }
 }
+
+/// Set the volume (e.g. "0.5" or a relative "0.1+") and report the result.
+async fn change_volume(player: &str, volume: &str) -> Result<String, String> {
+    run_playerctl(player, &["volume", volume]).await?;
+    run_playerctl(player, &["volume"])
+        .await
+        .map(|volume| format_volume(&volume))
+}
+
+fn format_volume(volume: &str) -> String {
+    match volume.trim().parse::<f64>() {
+        Ok(volume) => format!("Volume: {:.0}%", volume * 100.0),
+        Err(_) => format!("Volume: {}", volume.trim()),
+    }
+}
@@ -0,0 +1,822 @@
+use nix::unistd::{Group, getgroups, getuid};
+use rmcp::model::{CallToolResult, Content};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::Mutex;
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+
+use super::elixir_projects::{expand_path, find_elixir_projects, run_with_timeout};
+use crate::config::load_config;
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct CronRequest {
+    #[schemars(description = "Action to perform: list (default) or add")]
+    pub action: Option<String>,
+    #[schemars(description = "Schedule for add, e.g. '*/5 * * * *' or '@daily'")]
+    pub schedule: Option<String>,
+    #[schemars(description = "Command for add")]
+    pub command: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct PackageRequest {
+    #[schemars(description = "Action to perform: list_updates, install, update")]
+    pub action: String,
+    #[schemars(description = "Package name (required for install, optional for update)")]
+    pub package: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct AsdfRequest {
+    #[schemars(
+        description = "Action to perform: list_all, current, install, set_local, check_projects"
+    )]
+    pub action: String,
+    #[schemars(description = "Plugin name, e.g. elixir or erlang (list_all, install, set_local)")]
+    pub plugin: Option<String>,
+    #[schemars(description = "Version to install or set (install, set_local)")]
+    pub version: Option<String>,
+    #[schemars(
+        description = "Directory whose .tool-versions set_local writes (default: current directory)"
+    )]
+    pub path: Option<String>,
+}
+
+/// Aggregate CPU jiffies from the `cpu` line of `/proc/stat`.
+#[derive(Clone, Copy)]
+struct ProcStatSnapshot {
+    idle: u64,
+    total: u64,
+}
+
+static LAST_CPU_SNAPSHOT: Mutex<Option<ProcStatSnapshot>> = Mutex::new(None);
+
+pub fn handle_whoami() -> CallToolResult {
+    let var = |name: &str| env::var(name).unwrap_or_else(|_| "(unset)".to_string());
+
+    let uid = getuid();
+    let groups = match getgroups() {
+        Ok(gids) => gids
+            .iter()
+            .map(|gid| match Group::from_gid(*gid) {
+                Ok(Some(group)) => format!("{}({})", group.name, gid),
+                _ => gid.to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join(", "),
+        Err(e) => format!("unavailable: {}", e),
+    };
+
+    CallToolResult::success(vec![Content::text(format!(
+        "User: {}\nHome: {}\nShell: {}\nUID: {}\nGroups: {}",
+        var("USER"),
+        var("HOME"),
+        var("SHELL"),
+        uid,
+        groups
+    ))])
+}
+
+pub fn handle_uptime() -> CallToolResult {
+    let uptime = fs::read_to_string("/proc/uptime")
+        .ok()
+        .and_then(|content| content.split_whitespace().next()?.parse::<f64>().ok());
+    let loadavg = fs::read_to_string("/proc/loadavg").ok();
+
+    let output = match (uptime, loadavg) {
+        (Some(seconds), Some(loadavg)) => {
+            let total_minutes = (seconds / 60.0) as u64;
+            let days = total_minutes / (24 * 60);
+            let hours = (total_minutes / 60) % 24;
+            let minutes = total_minutes % 60;
+            let loads: Vec<&str> = loadavg.split_whitespace().take(3).collect();
+            format!(
+                "Up {} days, {} hours, {} minutes\nLoad average (1/5/15 min): {}",
+                days,
+                hours,
+                minutes,
+                loads.join(" ")
+            )
+        }
+        _ => "Failed to read /proc/uptime or /proc/loadavg".to_string(),
+    };
+
+    CallToolResult::success(vec![Content::text(output)])
+}
+
+pub fn handle_memory_info() -> CallToolResult {
+    let Ok(meminfo) = fs::read_to_string("/proc/meminfo") else {
+        return CallToolResult::success(vec![Content::text(
+            "Failed to read /proc/meminfo".to_string(),
+        )]);
+    };
+
+    // Values are reported in kB, e.g. "MemTotal:       16318412 kB"
+    let values: HashMap<&str, u64> = meminfo
+        .lines()
+        .filter_map(|line| {
+            let (key, rest) = line.split_once(':')?;
+            let kb = rest.split_whitespace().next()?.parse().ok()?;
+            Some((key, kb))
+        })
+        .collect();
+    let get = |key: &str| values.get(key).copied().unwrap_or(0);
+
+    let mem_total = get("MemTotal");
+    let mem_used = mem_total.saturating_sub(get("MemAvailable"));
+    let swap_total = get("SwapTotal");
+    let swap_used = swap_total.saturating_sub(get("SwapFree"));
+
+    CallToolResult::success(vec![Content::text(format!(
+        "Memory: {} / {} used ({:.1}%)\nSwap: {} / {} used ({:.1}%)\nBuffers: {}\nCached: {}",
+        format_kib(mem_used),
+        format_kib(mem_total),
+        percent(mem_used, mem_total),
+        format_kib(swap_used),
+        format_kib(swap_total),
+        percent(swap_used, swap_total),
+        format_kib(get("Buffers")),
+        format_kib(get("Cached")),
+    ))])
+}
+
+pub fn handle_cpu_info() -> CallToolResult {
+    let cpuinfo = fs::read_to_string("/proc/cpuinfo").unwrap_or_default();
+    let model = cpuinfo
+        .lines()
+        .find_map(|line| line.strip_prefix("model name"))
+        .and_then(|rest| rest.split_once(':'))
+        .map(|(_, name)| name.trim().to_string())
+        .unwrap_or_else(|| "unknown".to_string());
+    let cores = cpuinfo
+        .lines()
+        .filter(|line| line.starts_with("processor"))
+        .count();
+
+    let Some(current) = read_proc_stat() else {
+        return CallToolResult::success(vec![Content::text(
+            "Failed to read /proc/stat".to_string(),
+        )]);
+    };
+
+    let mut last = LAST_CPU_SNAPSHOT.lock().unwrap_or_else(|e| e.into_inner());
+    let (usage_label, baseline) = match *last {
+        Some(previous) => ("since last call", previous),
+        None => ("since boot", ProcStatSnapshot { idle: 0, total: 0 }),
+    };
+    *last = Some(current);
+
+    let total = current.total.saturating_sub(baseline.total);
+    let idle = current.idle.saturating_sub(baseline.idle);
+
+    CallToolResult::success(vec![Content::text(format!(
+        "Model: {}\nCores: {}\nUtilization ({}): {:.1}%",
+        model,
+        cores,
+        usage_label,
+        percent(total.saturating_sub(idle), total)
+    ))])
+}
+
+fn read_proc_stat() -> Option<ProcStatSnapshot> {
+    let stat = fs::read_to_string("/proc/stat").ok()?;
+    // cpu  user nice system idle iowait irq softirq steal ...
+    let fields: Vec<u64> = stat
+        .lines()
+        .next()?
+        .split_whitespace()
+        .skip(1)
+        .filter_map(|v| v.parse().ok())
+        .collect();
+
+    let idle = fields.get(3).copied().unwrap_or(0) + fields.get(4).copied().unwrap_or(0);
+    let total = fields.iter().take(8).sum();
+    Some(ProcStatSnapshot { idle, total })
+}
+
+fn format_kib(kib: u64) -> String {
+    if kib >= 1024 * 1024 {
+        format!("{:.1} GiB", kib as f64 / (1024.0 * 1024.0))
+    } else {
+        format!("{:.1} MiB", kib as f64 / 1024.0)
+    }
+}
+
+fn percent(part: u64, whole: u64) -> f64 {
+    if whole == 0 {
+        0.0
+    } else {
+        part as f64 / whole as f64 * 100.0
+    }
+}
+
+pub fn handle_temperature() -> CallToolResult {
+    let mut sensors: Vec<(String, f64)> = Vec::new();
+
+    for zone in dir_entries_with_prefix(Path::new("/sys/class/thermal"), "thermal_zone") {
+        let name = read_trimmed(&zone.join("type")).unwrap_or_else(|| file_name(&zone));
+        if let Some(celsius) = read_millidegrees(&zone.join("temp")) {
+            sensors.push((name, celsius));
+        }
+    }
+
+    for hwmon in dir_entries_with_prefix(Path::new("/sys/class/hwmon"), "hwmon") {
+        let chip = read_trimmed(&hwmon.join("name")).unwrap_or_else(|| file_name(&hwmon));
+        for input in dir_entries_with_prefix(&hwmon, "temp") {
+            let input_name = file_name(&input);
+            let Some(index) = input_name
+                .strip_prefix("temp")
+                .and_then(|rest| rest.strip_suffix("_input"))
+            else {
+                continue;
+            };
+            let label = read_trimmed(&hwmon.join(format!("temp{}_label", index)))
+                .unwrap_or_else(|| format!("temp{}", index));
+            if let Some(celsius) = read_millidegrees(&input) {
+                sensors.push((format!("{} {}", chip, label), celsius));
+            }
+        }
+    }
+
+    if sensors.is_empty() {
+        return CallToolResult::success(vec![Content::text(
+            "No temperature sensors found".to_string(),
+        )]);
+    }
+
+    let width = sensors
+        .iter()
+        .map(|(name, _)| name.len())
+        .max()
+        .unwrap_or(0);
+    let lines: Vec<String> = sensors
+        .iter()
+        .map(|(name, celsius)| {
+            let warning = if *celsius > 80.0 { " ⚠️" } else { "" };
+            format!("{:<width$}  {:>5.1}°C{}", name, celsius, warning)
+        })
+        .collect();
+
+    CallToolResult::success(vec![Content::text(lines.join("\n"))])
+}
+
+pub(crate) fn dir_entries_with_prefix(dir: &Path, prefix: &str) -> Vec<PathBuf> {
+    let mut entries: Vec<PathBuf> = fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .filter_map(|e| e.ok())
+                .filter(|e| e.file_name().to_string_lossy().starts_with(prefix))
+                .map(|e| e.path())
+                .collect()
+        })
+        .unwrap_or_default();
+    entries.sort();
+    entries
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| path.display().to_string())
+}
+
+pub(crate) fn read_trimmed(path: &Path) -> Option<String> {
+    fs::read_to_string(path).ok().map(|s| s.trim().to_string())
+}
+
+/// Sysfs temperatures are integers in millidegrees Celsius.
+fn read_millidegrees(path: &Path) -> Option<f64> {
+    read_trimmed(path)?
+        .parse::<i64>()
+        .ok()
+        .map(|m| m as f64 / 1000.0)
+}
+
+pub async fn handle_cron(req: CronRequest) -> CallToolResult {
+    let result = match req.action.as_deref().unwrap_or("list") {
+        "list" => Ok(handle_cron_list().await),
+        "add" => add_cron_entry(&req).await,
+        other => Err(format!("Unknown action '{}'. Use: list, add", other)),
+    };
+
+    match result {
+        Ok(output) => CallToolResult::success(vec![Content::text(output)]),
+        Err(e) => CallToolResult::success(vec![Content::text(e)]),
+    }
+}
+
+async fn handle_cron_list() -> String {
+    // (source, schedule, command)
+    let mut entries: Vec<(String, String, String)> = Vec::new();
+
+    let user_crontab = read_user_crontab().await.unwrap_or_default();
+    for line in crontab_lines(&user_crontab) {
+        if let Some((schedule, command)) = split_cron_line(line, false) {
+            entries.push(("crontab".to_string(), schedule, command));
+        }
+    }
+
+    // System crontabs have an extra user field before the command
+    for file in dir_entries_with_prefix(Path::new("/etc/cron.d"), "") {
+        let content = fs::read_to_string(&file).unwrap_or_default();
+        for line in crontab_lines(&content) {
+            if let Some((schedule, command)) = split_cron_line(line, true) {
+                entries.push((file.display().to_string(), schedule, command));
+            }
+        }
+    }
+
+    for (dir, schedule) in [
+        ("/etc/cron.hourly", "@hourly"),
+        ("/etc/cron.daily", "@daily"),
+    ] {
+        for script in dir_entries_with_prefix(Path::new(dir), "") {
+            entries.push((
+                dir.to_string(),
+                schedule.to_string(),
+                script.display().to_string(),
+            ));
+        }
+    }
+
+    if entries.is_empty() {
+        return "No cron entries found".to_string();
+    }
+
+    entries
+        .iter()
+        .map(|(source, schedule, command)| format!("[{}] {}  {}", source, schedule, command))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+async fn add_cron_entry(req: &CronRequest) -> Result<String, String> {
+    let (Some(schedule), Some(command)) = (&req.schedule, &req.command) else {
+        return Err("Error: 'schedule' and 'command' are required for add".to_string());
+    };
+    if !is_valid_schedule(schedule) {
+        return Err(format!("Invalid cron schedule '{}'", schedule));
+    }
+    if command.contains('\n') {
+        return Err("Error: command must be a single line".to_string());
+    }
+
+    let mut crontab = read_user_crontab().await?;
+    if !crontab.is_empty() && !crontab.ends_with('\n') {
+        crontab.push('\n');
+    }
+    let entry = format!("{} {}", schedule.trim(), command.trim());
+    crontab.push_str(&entry);
+    crontab.push('\n');
+
+    let mut child = Command::new("crontab")
+        .arg("-")
+        .stdin(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to run crontab: {}", e))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(crontab.as_bytes())
+            .await
+            .map_err(|e| format!("Failed to write crontab: {}", e))?;
+    }
+    let output = child
+        .wait_with_output()
+        .await
+        .map_err(|e| format!("Failed to run crontab: {}", e))?;
+
+    if output.status.success() {
+        Ok(format!("✓ Added: {}", entry))
+    } else {
+        Err(format!(
+            "crontab error: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ))
+    }
+}
+
+/// The user's crontab, empty if there is none yet. Any other failure is an
+/// error, so a crontab that could not be read is never replaced.
+async fn read_user_crontab() -> Result<String, String> {
+    let output = Command::new("crontab")
+        .arg("-l")
+        .output()
+        .await
+        .map_err(|e| format!("Failed to run crontab -l: {}", e))?;
+    if output.status.success() {
+        return Ok(String::from_utf8_lossy(&output.stdout).to_string());
+    }
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if stderr.contains("no crontab for") {
+        Ok(String::new())
+    } else {
+        Err(format!("✗ Failed to read crontab: {}", stderr.trim()))
+    }
+}
+
+/// Non-empty, non-comment lines that are not `NAME=value` assignments.
+fn crontab_lines(content: &str) -> impl Iterator<Item = &str> {
+    content.lines().map(str::trim).filter(|line| {
+        !line.is_empty()
+            && !line.starts_with('#')
+            && !line
+                .split_whitespace()
+                .next()
+                .is_some_and(|first| first.contains('='))
+    })
+}
+
+fn split_cron_line(line: &str, has_user: bool) -> Option<(String, String)> {
+    let fields: Vec<&str> = line.split_whitespace().collect();
+    let schedule_len = if fields.first()?.starts_with('@') {
+        1
+    } else {
+        5
+    };
+    let command_start = schedule_len + usize::from(has_user);
+    if fields.len() <= command_start {
+        return None;
+    }
+    Some((
+        fields[..schedule_len].join(" "),
+        fields[command_start..].join(" "),
+    ))
+}
+
+fn is_valid_schedule(schedule: &str) -> bool {
+    const SPECIAL: [&str; 8] = [
+        "@reboot",
+        "@yearly",
+        "@annually",
+        "@monthly",
+        "@weekly",
+        "@daily",
+        "@midnight",
+        "@hourly",
+    ];
+
+    let fields: Vec<&str> = schedule.split_whitespace().collect();
+    match fields.as_slice() {
+        [special] => SPECIAL.contains(special),
+        [_, _, _, _, _] => fields.iter().all(|field| {
+            field
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '*' | '/' | ',' | '-'))
+        }),
+        _ => false,
+    }
+}
+
+pub async fn handle_journal_errors() -> CallToolResult {
+    let output = Command::new("journalctl")
+        .args([
+            "-p",
+            "3",
+            "--since",
+            "1 hour ago",
+            "--no-pager",
+            "-n",
+            "50",
+            "--output=short-iso",
+        ])
+        .output()
+        .await;
+
+    let stdout = match output {
+        Ok(o) if o.status.success() => String::from_utf8_lossy(&o.stdout).to_string(),
+        Ok(o) => {
+            return CallToolResult::success(vec![Content::text(format!(
+                "journalctl error: {}",
+                String::from_utf8_lossy(&o.stderr).trim()
+            ))]);
+        }
+        Err(e) => {
+            return CallToolResult::success(vec![Content::text(format!(
+                "Failed to run journalctl: {}",
+                e
+            ))]);
+        }
+    };
+
+    // Lines look like "2024-05-01T10:00:00+0200 host unit[123]: message"
+    let lines: Vec<&str> = stdout
+        .lines()
+        .filter(|line| !line.starts_with("-- "))
+        .collect();
+    if lines.is_empty() {
+        return CallToolResult::success(vec![Content::text(
+            "No errors in the journal in the last hour".to_string(),
+        )]);
+    }
+
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for line in &lines {
+        let unit = line
+            .split_whitespace()
+            .nth(2)
+            .map(|u| u.split('[').next().unwrap_or(u).trim_end_matches(':'))
+            .unwrap_or("unknown");
+        *counts.entry(unit).or_default() += 1;
+    }
+    let mut counts: Vec<(&str, usize)> = counts.into_iter().collect();
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+
+    let summary: Vec<String> = counts
+        .iter()
+        .map(|(unit, count)| format!("{:>4}  {}", count, unit))
+        .collect();
+
+    CallToolResult::success(vec![Content::text(format!(
+        "{} errors in the last hour by unit:\n{}\n\n{}",
+        lines.len(),
+        summary.join("\n"),
+        lines.join("\n")
+    ))])
+}
+
+pub async fn handle_package(req: PackageRequest) -> CallToolResult {
+    let Some(manager) = ["pacman", "apt-get", "dnf", "brew"]
+        .into_iter()
+        .find(|bin| find_in_path(bin).is_some())
+    else {
+        return CallToolResult::success(vec![Content::text(
+            "No supported package manager found (pacman, apt-get, dnf, brew)".to_string(),
+        )]);
+    };
+
+    let package = req.package.as_deref();
+    // Package managers would read a leading '-' as one of their own options
+    if let Some(package) = package.filter(|p| p.starts_with('-')) {
+        return CallToolResult::success(vec![Content::text(format!(
+            "Error: invalid package name '{}'",
+            package
+        ))]);
+    }
+
+    let result = match req.action.as_str() {
+        "list_updates" => list_package_updates(manager).await,
+        "install" => match package {
+            Some(package) => {
+                let args: Vec<&str> = match manager {
+                    "pacman" => vec!["pacman", "-S", "--noconfirm", package],
+                    "apt-get" => vec!["apt-get", "install", "-y", package],
+                    "dnf" => vec!["dnf", "install", "-y", package],
+                    _ => vec!["brew", "install", package],
+                };
+                run_package_manager(&args).await
+            }
+            None => Err("Error: 'package' is required for install".to_string()),
+        },
+        "update" => {
+            let mut args: Vec<&str> = match manager {
+                "pacman" if package.is_some() => vec!["pacman", "-S", "--noconfirm"],
+                "pacman" => vec!["pacman", "-Syu", "--noconfirm"],
+                "apt-get" if package.is_some() => {
+                    vec!["apt-get", "install", "--only-upgrade", "-y"]
+                }
+                "apt-get" => vec!["apt-get", "upgrade", "-y"],
+                "dnf" => vec!["dnf", "upgrade", "-y"],
+                _ => vec!["brew", "upgrade"],
+            };
+            args.extend(package);
+            run_package_manager(&args).await
+        }
+        _ => Err(format!(
+            "Unknown action '{}'. Use: list_updates, install, update",
+            req.action
+        )),
+    };
+
+    match result {
+        Ok(output) => CallToolResult::success(vec![Content::text(output)]),
+        Err(e) => CallToolResult::success(vec![Content::text(e)]),
+    }
+}
+
+async fn list_package_updates(manager: &str) -> Result<String, String> {
+    let args: &[&str] = match manager {
+        "pacman" => &["-Qu"],
+        "apt-get" => &["list", "--upgradable"],
+        "dnf" => &["check-update", "-q"],
+        _ => &["outdated", "--verbose"],
+    };
+    let program = if manager == "apt-get" { "apt" } else { manager };
+
+    let timeout = Duration::from_secs(load_config().command_timeout_secs);
+    let output = run_with_timeout(Command::new(program).args(args), timeout)
+        .await
+        .map_err(|e| format!("Failed to run {}: {}", program, e))?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    let updates: Vec<String> = stdout
+        .lines()
+        .filter(|line| !line.trim().is_empty() && !line.starts_with("Listing"))
+        .map(|line| match manager {
+            // "name/suite 1.2 amd64 [upgradable from: 1.1]"
+            "apt-get" => {
+                let fields: Vec<&str> = line.split_whitespace().collect();
+                let name = fields[0].split('/').next().unwrap_or(fields[0]);
+                let available = fields.get(1).copied().unwrap_or("?");
+                let current = fields
+                    .last()
+                    .map(|f| f.trim_end_matches(']'))
+                    .unwrap_or("?");
+                format!("{} {} -> {}", name, current, available)
+            }
+            // "name.arch  1.2-3.fc40  updates"
+            "dnf" => {
+                let fields: Vec<&str> = line.split_whitespace().collect();
+                format!("{} -> {}", fields[0], fields.get(1).copied().unwrap_or("?"))
+            }
+            // pacman already prints "name 1.1-1 -> 1.2-1", brew "name (1.1) < 1.2"
+            _ => line.trim().to_string(),
+        })
+        .collect();
+
+    if updates.is_empty() {
+        Ok(format!("✅ No updates available ({})", manager))
+    } else {
+        Ok(format!(
+            "{} updates available ({}):\n{}",
+            updates.len(),
+            manager,
+            updates.join("\n")
+        ))
+    }
+}
+
+/// Run a package manager command, via non-interactive sudo for system package managers.
+async fn run_package_manager(args: &[&str]) -> Result<String, String> {
+    let mut cmd = if args[0] == "brew" {
+        Command::new("brew")
+    } else {
+        let mut sudo = Command::new("sudo");
+        sudo.args(["-n", args[0]]);
+        sudo
+    };
+    cmd.args(&args[1..]);
+
+    let timeout = Duration::from_secs(load_config().command_timeout_secs);
+    let output = run_with_timeout(&mut cmd, timeout)
+        .await
+        .map_err(|e| format!("Failed to run {}: {}", args[0], e))?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    if output.status.success() {
+        Ok(format!("✓ {}\n{}", args.join(" "), stdout.trim_end()))
+    } else {
+        Err(format!(
+            "✗ {}: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ))
+    }
+}
+
+pub(crate) fn find_in_path(bin: &str) -> Option<PathBuf> {
+    env::var_os("PATH").and_then(|paths| {
+        env::split_paths(&paths)
+            .map(|dir| dir.join(bin))
+            .find(|candidate| candidate.is_file())
+    })
+}
+
+pub async fn handle_asdf(req: AsdfRequest) -> CallToolResult {
+    let plugin = req.plugin.as_deref();
+    let version = req.version.as_deref();
+
+    let result = match (req.action.as_str(), plugin, version) {
+        ("list_all", Some(plugin), _) => run_asdf(&["list", "all", plugin], None).await,
+        ("current", _, _) => run_asdf(&["current"], None).await,
+        ("install", Some(plugin), Some(version)) => run_asdf(&["install", plugin, version], None)
+            .await
+            .map(|_| format!("✓ Installed {} {}", plugin, version)),
+        ("set_local", Some(plugin), Some(version)) => {
+            let dir = req.path.as_deref().map(expand_path);
+            run_asdf(&["local", plugin, version], dir.as_deref())
+                .await
+                .map(|_| format!("✓ Set {} {} in .tool-versions", plugin, version))
+        }
+        ("check_projects", _, _) => check_project_tool_versions().await,
+        ("list_all", None, _) | ("install" | "set_local", None, _) => Err(format!(
+            "Error: 'plugin' is required for {} action",
+            req.action
+        )),
+        ("install" | "set_local", Some(_), None) => Err(format!(
+            "Error: 'version' is required for {} action",
+            req.action
+        )),
+        _ => Err(format!(
+            "Unknown action '{}'. Use: list_all, current, install, set_local, check_projects",
+            req.action
+        )),
+    };
+
+    match result {
+        Ok(output) => CallToolResult::success(vec![Content::text(output)]),
+        Err(e) => CallToolResult::success(vec![Content::text(e)]),
+    }
+}
+
+/// Compare each project's `.tool-versions` with the global asdf versions.
+async fn check_project_tool_versions() -> Result<String, String> {
+    // Outside any project, `asdf current` reports the global versions
+    let home = dirs::home_dir().unwrap_or_else(|| PathBuf::from("/"));
+    let current: HashMap<String, String> = run_asdf(&["current"], Some(&home))
+        .await?
+        .lines()
+        .filter(|line| !line.starts_with("Name"))
+        .filter_map(|line| {
+            let mut words = line.split_whitespace();
+            Some((words.next()?.to_string(), words.next()?.to_string()))
+        })
+        .collect();
+
+    let mut lines = Vec::new();
+    for project in find_elixir_projects(None) {
+        let project_name = file_name(&project);
+        let differing: Vec<String> = read_tool_versions(&project)
+            .into_iter()
+            .filter(|(tool, version)| current.get(tool) != Some(version))
+            .map(|(tool, version)| {
+                let global = current.get(&tool).map_or("none", |v| v.as_str());
+                format!("{} {} (current: {})", tool, version, global)
+            })
+            .collect();
+        if !differing.is_empty() {
+            lines.push(format!("{} {}", project_name, differing.join(", ")));
+        }
+    }
+
+    if lines.is_empty() {
+        Ok("✓ All projects use the current versions".to_string())
+    } else {
+        Ok(lines.join("\n"))
+    }
+}
+
+/// Parse `<tool> <version>` lines from `dir/.tool-versions`, taking the first
+/// version when fallbacks are listed.
+pub(crate) fn read_tool_versions(dir: &Path) -> Vec<(String, String)> {
+    fs::read_to_string(dir.join(".tool-versions"))
+        .map(|content| {
+            content
+                .lines()
+                .map(|line| line.split('#').next().unwrap_or_default())
+                .filter_map(|line| {
+                    let mut words = line.split_whitespace();
+                    Some((words.next()?.to_string(), words.next()?.to_string()))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+async fn run_asdf(args: &[&str], dir: Option<&Path>) -> Result<String, String> {
+    let mut cmd = Command::new("asdf");
+    cmd.args(args);
+    if let Some(dir) = dir {
+        cmd.current_dir(dir);
+    }
+
+    // `asdf install` may compile Erlang for a long time
+    let timeout = Duration::from_secs(load_config().command_timeout_secs);
+    match run_with_timeout(&mut cmd, timeout).await {
+        Ok(output) => {
+            if output.status.success() {
+                Ok(String::from_utf8_lossy(&output.stdout).to_string())
+            } else {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                Err(format!("asdf error: {}", stderr.trim()))
+            }
+        }
+        Err(e) => Err(format!("Failed to run asdf: {}", e)),
+    }
+}
+
+pub async fn handle_mise_current() -> CallToolResult {
+    let mut sections = Vec::new();
+    for args in [&["current"][..], &["ls", "--current"][..]] {
+        let text = match Command::new("mise").args(args).output().await {
+            Ok(output) if output.status.success() => String::from_utf8_lossy(&output.stdout)
+                .trim_end()
+                .to_string(),
+            Ok(output) => format!(
+                "mise error: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            ),
+            Err(e) => format!("Failed to run mise: {}", e),
+        };
+        sections.push(format!("$ mise {}\n{}", args.join(" "), text));
+    }
+
+    CallToolResult::success(vec![Content::text(sections.join("\n\n"))])
+}
@@ -0,0 +1,1052 @@
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use chrono::{DateTime, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use chrono_tz::Tz;
+use rand::seq::{IndexedRandom, SliceRandom};
+use regex::RegexBuilder;
+use rmcp::model::{CallToolResult, Content};
+use schemars::JsonSchema;
+use semver::{BuildMetadata, Prerelease, Version};
+use serde::{Deserialize, Serialize};
+use similar::TextDiff;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tokio::process::Command;
+
+use super::desktop::pipe_through;
+use super::elixir_projects::{expand_path, run_with_timeout};
+use super::system::find_in_path;
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct PasswordGenRequest {
+    #[schemars(description = "Password length (default 20, max 256)")]
+    pub length: Option<usize>,
+    #[schemars(description = "Include symbols (default true)")]
+    pub include_symbols: Option<bool>,
+    #[schemars(description = "Include numbers (default true)")]
+    pub include_numbers: Option<bool>,
+    #[schemars(description = "Number of passwords to generate (default 1, max 20)")]
+    pub count: Option<usize>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct JwtDecodeRequest {
+    #[schemars(description = "JSON Web Token to decode (the signature is not verified)")]
+    pub token: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ValidateRequest {
+    #[schemars(description = "Document to validate")]
+    pub content: String,
+    #[schemars(description = "Format of the document: toml, yaml, or json")]
+    pub format: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct RegexTestRequest {
+    #[schemars(description = "Regular expression (Rust regex syntax)")]
+    pub pattern: String,
+    #[schemars(description = "Text to match against")]
+    pub input: String,
+    #[schemars(
+        description = "Flag characters: i (case insensitive), m (multiline), s (dot matches newline)"
+    )]
+    pub flags: Option<String>,
+    #[schemars(
+        description = "Return all non-overlapping matches instead of the first (default false)"
+    )]
+    pub find_all: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SemverRequest {
+    #[schemars(description = "Action to perform: parse, compare, bump")]
+    pub action: String,
+    #[schemars(description = "Version to parse, compare or bump (a leading 'v' is ignored)")]
+    pub version_a: String,
+    #[schemars(description = "Version to compare against (compare only)")]
+    pub version_b: Option<String>,
+    #[schemars(description = "Component to bump: major, minor, or patch (default)")]
+    pub bump_type: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct TimeRequest {
+    #[schemars(
+        description = "Timestamp as RFC 3339, RFC 2822, Unix epoch seconds/milliseconds, or e.g. '2024-05-01 14:30' (default: now)"
+    )]
+    pub timestamp: Option<String>,
+    #[schemars(
+        description = "IANA timezone for timestamps without an offset, e.g. Europe/Berlin (default UTC)"
+    )]
+    pub from_tz: Option<String>,
+    #[schemars(description = "IANA timezone to convert to (default UTC)")]
+    pub to_tz: Option<String>,
+    #[schemars(description = "strftime pattern for the output (default '%Y-%m-%d %H:%M:%S %Z')")]
+    pub format: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ColorRequest {
+    #[schemars(
+        description = "Color as #RRGGBB, #RGB, rgb(r, g, b), hsl(h, s%, l%) or a CSS color name"
+    )]
+    pub input: String,
+    #[schemars(description = "Output format: hex, rgb, or hsl (default: all)")]
+    pub output_format: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ManPageRequest {
+    #[schemars(description = "Command or topic to look up, e.g. 'tar'")]
+    pub command: String,
+    #[schemars(description = "Manual section, e.g. 1 for commands or 5 for file formats")]
+    pub section: Option<u8>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct TldrRequest {
+    #[schemars(description = "Command to look up, e.g. 'tar'")]
+    pub command: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct TimerRequest {
+    #[schemars(
+        description = "Action to perform: start, stop, status (stopwatch) or timer (countdown)"
+    )]
+    pub action: String,
+    #[schemars(description = "Stopwatch or timer name (default 'default')")]
+    pub label: Option<String>,
+    #[schemars(description = "Countdown length in seconds for timer (max 3600)")]
+    pub duration_secs: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct WordCountRequest {
+    #[schemars(description = "Text to count (either text or file is required)")]
+    pub text: Option<String>,
+    #[schemars(description = "Path of a file to count")]
+    pub file: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct DiffRequest {
+    #[schemars(description = "Original text")]
+    pub old_text: String,
+    #[schemars(description = "Changed text")]
+    pub new_text: String,
+    #[schemars(description = "Unchanged lines shown around each change (default 3)")]
+    pub context_lines: Option<usize>,
+    #[schemars(description = "Label for the original text in the diff header (default 'old')")]
+    pub old_label: Option<String>,
+    #[schemars(description = "Label for the changed text in the diff header (default 'new')")]
+    pub new_label: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct TemplateRequest {
+    #[schemars(description = "Template text with {{variable}} placeholders")]
+    pub template: String,
+    #[schemars(description = "Values for the placeholders, by name")]
+    pub variables: HashMap<String, String>,
+}
+
+const TLDR_CACHE_TTL: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+/// Basic CSS color keywords.
+const COLOR_NAMES: &[(&str, (u8, u8, u8))] = &[
+    ("black", (0, 0, 0)),
+    ("white", (255, 255, 255)),
+    ("red", (255, 0, 0)),
+    ("lime", (0, 255, 0)),
+    ("blue", (0, 0, 255)),
+    ("yellow", (255, 255, 0)),
+    ("cyan", (0, 255, 255)),
+    ("aqua", (0, 255, 255)),
+    ("magenta", (255, 0, 255)),
+    ("fuchsia", (255, 0, 255)),
+    ("silver", (192, 192, 192)),
+    ("gray", (128, 128, 128)),
+    ("grey", (128, 128, 128)),
+    ("maroon", (128, 0, 0)),
+    ("olive", (128, 128, 0)),
+    ("green", (0, 128, 0)),
+    ("purple", (128, 0, 128)),
+    ("teal", (0, 128, 128)),
+    ("navy", (0, 0, 128)),
+    ("orange", (255, 165, 0)),
+    ("pink", (255, 192, 203)),
+    ("brown", (165, 42, 42)),
+    ("gold", (255, 215, 0)),
+    ("indigo", (75, 0, 130)),
+    ("violet", (238, 130, 238)),
+    ("coral", (255, 127, 80)),
+    ("salmon", (250, 128, 114)),
+    ("rebeccapurple", (102, 51, 153)),
+];
+
+const LOWERCASE: &[u8] = b"abcdefghijklmnopqrstuvwxyz";
+const UPPERCASE: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+const NUMBERS: &[u8] = b"0123456789";
+const SYMBOLS: &[u8] = b"!@#$%^&*()-_=+[]{};:,.<>?/~";
+
+pub fn handle_generate_password(req: PasswordGenRequest) -> CallToolResult {
+    let mut classes = vec![LOWERCASE, UPPERCASE];
+    if req.include_numbers.unwrap_or(true) {
+        classes.push(NUMBERS);
+    }
+    if req.include_symbols.unwrap_or(true) {
+        classes.push(SYMBOLS);
+    }
+    let alphabet: Vec<u8> = classes.concat();
+    let length = req.length.unwrap_or(20).clamp(classes.len(), 256);
+    let count = req.count.unwrap_or(1).clamp(1, 20);
+
+    let mut rng = rand::rng();
+    let passwords: Vec<String> = (0..count)
+        .map(|_| {
+            // One character from every class, then fill from the whole alphabet
+            let mut chars: Vec<u8> = classes
+                .iter()
+                .filter_map(|class| class.choose(&mut rng).copied())
+                .collect();
+            let fill: Vec<u8> = (chars.len()..length)
+                .filter_map(|_| alphabet.choose(&mut rng).copied())
+                .collect();
+            chars.extend(fill);
+            chars.shuffle(&mut rng);
+            String::from_utf8_lossy(&chars).to_string()
+        })
+        .collect();
+
+    CallToolResult::success(vec![Content::text(passwords.join("\n"))])
+}
+
+pub fn handle_jwt_decode(req: JwtDecodeRequest) -> CallToolResult {
+    let text = match decode_jwt(req.token.trim()) {
+        Ok(output) => output,
+        Err(e) => e,
+    };
+    CallToolResult::success(vec![Content::text(text)])
+}
+
+fn decode_jwt(token: &str) -> Result<String, String> {
+    let parts: Vec<&str> = token.split('.').collect();
+    if parts.len() != 3 {
+        return Err(format!(
+            "Error: expected 3 dot-separated parts, found {}",
+            parts.len()
+        ));
+    }
+
+    let decode = |name: &str, part: &str| -> Result<serde_json::Value, String> {
+        let bytes = URL_SAFE_NO_PAD
+            .decode(part.trim_end_matches('='))
+            .map_err(|e| format!("Error: {} is not valid base64url: {}", name, e))?;
+        serde_json::from_slice(&bytes)
+            .map_err(|e| format!("Error: {} is not valid JSON: {}", name, e))
+    };
+    let header = decode("header", parts[0])?;
+    let payload = decode("payload", parts[1])?;
+
+    let pretty = |value: &serde_json::Value| {
+        serde_json::to_string_pretty(value).unwrap_or_else(|_| value.to_string())
+    };
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or_default();
+    let expiry = match payload["exp"].as_i64() {
+        Some(exp) => {
+            let at = chrono::DateTime::from_timestamp(exp, 0)
+                .map(|t| t.to_rfc3339())
+                .unwrap_or_else(|| exp.to_string());
+            if exp <= now {
+                format!("✗ Expired at {} ({}s ago)", at, now - exp)
+            } else {
+                format!("✓ Expires at {} (in {}s)", at, exp - now)
+            }
+        }
+        None => "- No exp claim".to_string(),
+    };
+
+    Ok(format!(
+        "Header:\n{}\n\nPayload:\n{}\n\n{}\nSignature: not verified",
+        pretty(&header),
+        pretty(&payload),
+        expiry
+    ))
+}
+
+pub fn handle_validate(req: ValidateRequest) -> CallToolResult {
+    // Each parser's error message already includes the line and column
+    let result = match req.format.to_lowercase().as_str() {
+        "toml" => toml::from_str::<toml::Value>(&req.content)
+            .map(|_| ())
+            .map_err(|e| e.to_string()),
+        "yaml" | "yml" => serde_yaml::from_str::<serde_yaml::Value>(&req.content)
+            .map(|_| ())
+            .map_err(|e| e.to_string()),
+        "json" => serde_json::from_str::<serde_json::Value>(&req.content)
+            .map(|_| ())
+            .map_err(|e| e.to_string()),
+        _ => {
+            return CallToolResult::success(vec![Content::text(format!(
+                "Unknown format '{}'. Use: toml, yaml, json",
+                req.format
+            ))]);
+        }
+    };
+
+    let text = match result {
+        Ok(_) => format!("Valid {}", req.format),
+        Err(e) => format!("Invalid {}: {}", req.format, e.trim()),
+    };
+    CallToolResult::success(vec![Content::text(text)])
+}
+
+pub fn handle_regex_test(req: RegexTestRequest) -> CallToolResult {
+    let text = match regex_test(&req) {
+        Ok(output) => output,
+        Err(e) => e,
+    };
+    CallToolResult::success(vec![Content::text(text)])
+}
+
+fn regex_test(req: &RegexTestRequest) -> Result<String, String> {
+    const MAX_MATCHES: usize = 100;
+
+    let mut builder = RegexBuilder::new(&req.pattern);
+    for flag in req.flags.as_deref().unwrap_or_default().chars() {
+        match flag {
+            'i' => builder.case_insensitive(true),
+            'm' => builder.multi_line(true),
+            's' => builder.dot_matches_new_line(true),
+            other => return Err(format!("Unknown flag '{}'. Use: i, m, s", other)),
+        };
+    }
+    let regex = builder
+        .build()
+        .map_err(|e| format!("Invalid pattern:\n{}", e))?;
+
+    let describe = |m: regex::Match| format!("{}..{}: {:?}", m.start(), m.end(), m.as_str());
+    if req.find_all.unwrap_or(false) {
+        let matches: Vec<String> = regex
+            .find_iter(&req.input)
+            .take(MAX_MATCHES)
+            .map(describe)
+            .collect();
+        if matches.is_empty() {
+            Ok("✗ No match".to_string())
+        } else {
+            Ok(format!(
+                "{} matches:\n{}",
+                matches.len(),
+                matches.join("\n")
+            ))
+        }
+    } else {
+        match regex.captures(&req.input) {
+            Some(captures) => {
+                let mut lines = vec![format!("✓ Match at {}", describe(captures.get_match()))];
+                for (i, group) in captures.iter().enumerate().skip(1) {
+                    let name = regex
+                        .capture_names()
+                        .nth(i)
+                        .flatten()
+                        .map_or_else(|| i.to_string(), str::to_string);
+                    match group {
+                        Some(m) => lines.push(format!("  group {} at {}", name, describe(m))),
+                        None => lines.push(format!("  group {} did not participate", name)),
+                    }
+                }
+                Ok(lines.join("\n"))
+            }
+            None => Ok("✗ No match".to_string()),
+        }
+    }
+}
+
+/// `version` with the `bump_type` component (major, minor or patch)
+/// incremented, dropping any pre-release and build metadata.
+pub(crate) fn bump_version(version: &Version, bump_type: &str) -> Result<Version, String> {
+    let mut bumped = version.clone();
+    match bump_type {
+        "major" => {
+            bumped.major += 1;
+            bumped.minor = 0;
+            bumped.patch = 0;
+        }
+        "minor" => {
+            bumped.minor += 1;
+            bumped.patch = 0;
+        }
+        "patch" => bumped.patch += 1,
+        other => {
+            return Err(format!(
+                "Unknown bump_type '{}'. Use: major, minor, patch",
+                other
+            ));
+        }
+    }
+    bumped.pre = Prerelease::EMPTY;
+    bumped.build = BuildMetadata::EMPTY;
+    Ok(bumped)
+}
+
+pub fn handle_semver(req: SemverRequest) -> CallToolResult {
+    let text = match semver_action(&req) {
+        Ok(output) => output,
+        Err(e) => e,
+    };
+    CallToolResult::success(vec![Content::text(text)])
+}
+
+fn semver_action(req: &SemverRequest) -> Result<String, String> {
+    let parse = |version: &str| {
+        Version::parse(version.trim().trim_start_matches('v'))
+            .map_err(|e| format!("Error: invalid version '{}': {}", version, e))
+    };
+    let a = parse(&req.version_a)?;
+
+    match req.action.as_str() {
+        "parse" => {
+            let or_none = |s: &str| {
+                if s.is_empty() {
+                    "(none)".to_string()
+                } else {
+                    s.to_string()
+                }
+            };
+            Ok(format!(
+                "Major: {}\nMinor: {}\nPatch: {}\nPre-release: {}\nBuild: {}",
+                a.major,
+                a.minor,
+                a.patch,
+                or_none(a.pre.as_str()),
+                or_none(a.build.as_str())
+            ))
+        }
+        "compare" => {
+            let b = parse(
+                req.version_b
+                    .as_deref()
+                    .ok_or("Error: 'version_b' is required for compare action")?,
+            )?;
+            // Build metadata does not affect precedence
+            let relation = match a.cmp_precedence(&b) {
+                Ordering::Less => "<",
+                Ordering::Equal => "=",
+                Ordering::Greater => ">",
+            };
+            Ok(format!("{} {} {}", a, relation, b))
+        }
+        "bump" => {
+            let bumped = bump_version(&a, req.bump_type.as_deref().unwrap_or("patch"))?;
+            Ok(format!("{} -> {}", a, bumped))
+        }
+        _ => Err(format!(
+            "Unknown action '{}'. Use: parse, compare, bump",
+            req.action
+        )),
+    }
+}
+
+pub fn handle_time_convert(req: TimeRequest) -> CallToolResult {
+    let text = match time_convert(&req) {
+        Ok(output) => output,
+        Err(e) => e,
+    };
+    CallToolResult::success(vec![Content::text(text)])
+}
+
+fn time_convert(req: &TimeRequest) -> Result<String, String> {
+    let parse_tz = |tz: Option<&str>| -> Result<Tz, String> {
+        tz.unwrap_or("UTC")
+            .parse::<Tz>()
+            .map_err(|_| format!("Error: unknown timezone '{}'", tz.unwrap_or_default()))
+    };
+    let from_tz = parse_tz(req.from_tz.as_deref())?;
+    let to_tz = parse_tz(req.to_tz.as_deref())?;
+
+    let utc = match req.timestamp.as_deref().map(str::trim) {
+        None | Some("") => Utc::now(),
+        Some(timestamp) => parse_timestamp(timestamp, from_tz)?,
+    };
+
+    let pattern = req.format.as_deref().unwrap_or("%Y-%m-%d %H:%M:%S %Z");
+    let mut original = String::new();
+    let mut converted = String::new();
+    write!(original, "{}", utc.with_timezone(&from_tz).format(pattern))
+        .and_then(|_| write!(converted, "{}", utc.with_timezone(&to_tz).format(pattern)))
+        .map_err(|_| format!("Error: invalid format '{}'", pattern))?;
+
+    Ok(format!(
+        "Original ({}): {}\nConverted ({}): {}\nUnix: {}",
+        from_tz,
+        original,
+        to_tz,
+        converted,
+        utc.timestamp()
+    ))
+}
+
+/// Parse RFC 3339, RFC 2822, Unix epoch seconds or milliseconds, or a common
+/// date format without an offset, which is taken to be in `tz`.
+fn parse_timestamp(timestamp: &str, tz: Tz) -> Result<DateTime<Utc>, String> {
+    if let Ok(epoch) = timestamp.parse::<i64>() {
+        // Anything past the year 5138 in seconds is taken to be milliseconds
+        let parsed = if epoch.abs() > 100_000_000_000 {
+            DateTime::from_timestamp_millis(epoch)
+        } else {
+            DateTime::from_timestamp(epoch, 0)
+        };
+        return parsed.ok_or_else(|| format!("Error: epoch {} is out of range", epoch));
+    }
+    if let Ok(dt) = DateTime::parse_from_rfc3339(timestamp) {
+        return Ok(dt.with_timezone(&Utc));
+    }
+    if let Ok(dt) = DateTime::parse_from_rfc2822(timestamp) {
+        return Ok(dt.with_timezone(&Utc));
+    }
+
+    const FORMATS: &[&str] = &[
+        "%Y-%m-%d %H:%M:%S%.f",
+        "%Y-%m-%dT%H:%M:%S%.f",
+        "%Y-%m-%d %H:%M",
+        "%Y/%m/%d %H:%M:%S",
+        "%d %b %Y %H:%M:%S",
+        "%b %d %Y %H:%M:%S",
+    ];
+    let naive = FORMATS
+        .iter()
+        .find_map(|f| NaiveDateTime::parse_from_str(timestamp, f).ok())
+        .or_else(|| {
+            NaiveDate::parse_from_str(timestamp, "%Y-%m-%d")
+                .ok()
+                .and_then(|d| d.and_hms_opt(0, 0, 0))
+        })
+        .ok_or_else(|| format!("Error: could not parse timestamp '{}'", timestamp))?;
+
+    tz.from_local_datetime(&naive)
+        .earliest()
+        .map(|dt| dt.with_timezone(&Utc))
+        .ok_or_else(|| format!("Error: {} does not exist in {}", timestamp, tz))
+}
+
+pub fn handle_color_convert(req: ColorRequest) -> CallToolResult {
+    let text = match color_convert(&req) {
+        Ok(output) => output,
+        Err(e) => e,
+    };
+    CallToolResult::success(vec![Content::text(text)])
+}
+
+fn color_convert(req: &ColorRequest) -> Result<String, String> {
+    let (r, g, b) = parse_color(&req.input)
+        .ok_or_else(|| format!("Error: could not parse color '{}'", req.input))?;
+    let (h, s, l) = rgb_to_hsl(r, g, b);
+
+    let hex = format!("#{:02x}{:02x}{:02x}", r, g, b);
+    let rgb = format!("rgb({}, {}, {})", r, g, b);
+    let hsl = format!("hsl({}, {}%, {}%)", h, s, l);
+
+    match req.output_format.as_deref() {
+        Some("hex") => Ok(hex),
+        Some("rgb") => Ok(rgb),
+        Some("hsl") => Ok(hsl),
+        None => {
+            let mut lines = vec![hex, rgb, hsl];
+            if let Some((name, _)) = COLOR_NAMES.iter().find(|(_, c)| *c == (r, g, b)) {
+                lines.push(name.to_string());
+            }
+            Ok(lines.join("\n"))
+        }
+        Some(other) => Err(format!(
+            "Unknown output_format '{}'. Use: hex, rgb, hsl",
+            other
+        )),
+    }
+}
+
+fn parse_color(input: &str) -> Option<(u8, u8, u8)> {
+    let input = input.trim().to_lowercase();
+
+    if let Some(hex) = input.strip_prefix('#') {
+        let channel = |s: &str| u8::from_str_radix(s, 16).ok();
+        return match hex.len() {
+            // #abc is shorthand for #aabbcc
+            3 if hex.is_ascii() => {
+                let digits: Vec<String> = hex.chars().map(|c| format!("{}{}", c, c)).collect();
+                Some((
+                    channel(&digits[0])?,
+                    channel(&digits[1])?,
+                    channel(&digits[2])?,
+                ))
+            }
+            6 if hex.is_ascii() => Some((
+                channel(&hex[0..2])?,
+                channel(&hex[2..4])?,
+                channel(&hex[4..6])?,
+            )),
+            _ => None,
+        };
+    }
+
+    let arguments = |prefix: &str| -> Option<Vec<f64>> {
+        let args = input
+            .strip_prefix(prefix)?
+            .trim_start_matches('a')
+            .strip_prefix('(')?
+            .strip_suffix(')')?;
+        args.split(',')
+            .take(3)
+            .map(|a| a.trim().trim_end_matches('%').parse::<f64>().ok())
+            .collect()
+    };
+    if let Some([r, g, b]) = arguments("rgb").as_deref() {
+        let channel = |v: f64| (0.0..=255.0).contains(&v).then_some(v.round() as u8);
+        return Some((channel(*r)?, channel(*g)?, channel(*b)?));
+    }
+    if let Some([h, s, l]) = arguments("hsl").as_deref() {
+        return Some(hsl_to_rgb(
+            h.rem_euclid(360.0),
+            s.clamp(0.0, 100.0),
+            l.clamp(0.0, 100.0),
+        ));
+    }
+
+    COLOR_NAMES
+        .iter()
+        .find(|(name, _)| *name == input)
+        .map(|(_, rgb)| *rgb)
+}
+
+/// Convert to hue in degrees and saturation/lightness in percent.
+fn rgb_to_hsl(r: u8, g: u8, b: u8) -> (u32, u32, u32) {
+    let (r, g, b) = (r as f64 / 255.0, g as f64 / 255.0, b as f64 / 255.0);
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let l = (max + min) / 2.0;
+    let delta = max - min;
+
+    if delta == 0.0 {
+        return (0, 0, (l * 100.0).round() as u32);
+    }
+    let s = delta / (1.0 - (2.0 * l - 1.0).abs());
+    let h = if max == r {
+        60.0 * ((g - b) / delta).rem_euclid(6.0)
+    } else if max == g {
+        60.0 * ((b - r) / delta + 2.0)
+    } else {
+        60.0 * ((r - g) / delta + 4.0)
+    };
+    (
+        h.round() as u32 % 360,
+        (s * 100.0).round() as u32,
+        (l * 100.0).round() as u32,
+    )
+}
+
+fn hsl_to_rgb(h: f64, s: f64, l: f64) -> (u8, u8, u8) {
+    let (s, l) = (s / 100.0, l / 100.0);
+    let c = (1.0 - (2.0 * l - 1.0).abs()) * s;
+    let x = c * (1.0 - ((h / 60.0).rem_euclid(2.0) - 1.0).abs());
+    let m = l - c / 2.0;
+    let (r, g, b) = match (h / 60.0) as u32 {
+        0 => (c, x, 0.0),
+        1 => (x, c, 0.0),
+        2 => (0.0, c, x),
+        3 => (0.0, x, c),
+        4 => (x, 0.0, c),
+        _ => (c, 0.0, x),
+    };
+    let channel = |v: f64| ((v + m) * 255.0).round() as u8;
+    (channel(r), channel(g), channel(b))
+}
+
+pub async fn handle_man_page(req: ManPageRequest) -> CallToolResult {
+    const MAX_CHARS: usize = 2000;
+
+    let command = req.command.trim();
+    if command.is_empty() || command.starts_with('-') || command.contains('/') {
+        return CallToolResult::success(vec![Content::text(format!(
+            "Error: invalid command '{}'",
+            req.command
+        ))]);
+    }
+
+    let text = match man_synopsis(command, req.section).await {
+        Ok(text) => text,
+        // Without man, the command's own help is the next best thing. Programs
+        // that don't know --help may just run, so keep them on a short leash.
+        Err(man_error) if find_in_path("man").is_none() && find_in_path(command).is_some() => {
+            match run_with_timeout(
+                Command::new(command).arg("--help").stdin(Stdio::null()),
+                Duration::from_secs(5),
+            )
+            .await
+            {
+                Ok(output) => {
+                    let stdout = String::from_utf8_lossy(&output.stdout);
+                    let stderr = String::from_utf8_lossy(&output.stderr);
+                    let help = if stdout.trim().is_empty() {
+                        stderr
+                    } else {
+                        stdout
+                    };
+                    format!("{} --help:\n{}", command, help.trim())
+                }
+                Err(e) => format!("{}\nFailed to run {} --help: {}", man_error, command, e),
+            }
+        }
+        Err(man_error) => man_error,
+    };
+
+    let mut capped: String = text.chars().take(MAX_CHARS).collect();
+    if text.chars().count() > MAX_CHARS {
+        capped.push_str("...");
+    }
+    CallToolResult::success(vec![Content::text(capped)])
+}
+
+/// The NAME and SYNOPSIS sections of a man page, as plain text.
+async fn man_synopsis(command: &str, section: Option<u8>) -> Result<String, String> {
+    let section = section.map(|s| s.to_string());
+    let mut cmd = Command::new("man");
+    cmd.args(section.as_deref())
+        .arg(command)
+        .env("MANWIDTH", "80");
+
+    let output = cmd
+        .output()
+        .await
+        .map_err(|e| format!("Failed to run man: {}", e))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("man error: {}", stderr.trim()));
+    }
+    // Strip the backspace overstrikes man uses for bold and underline
+    let plain = pipe_through("col", &["-b"], &output.stdout).await?;
+    let page = String::from_utf8_lossy(&plain);
+
+    // Section headers are the unindented lines, e.g. "NAME" or "SYNOPSIS"
+    let mut sections: Vec<&str> = Vec::new();
+    let mut keep = false;
+    for line in page.lines() {
+        if !line.is_empty() && !line.starts_with(char::is_whitespace) {
+            keep = matches!(line.trim(), "NAME" | "SYNOPSIS");
+        }
+        if keep {
+            sections.push(line);
+        }
+    }
+
+    if sections.is_empty() {
+        Ok(page.trim().to_string())
+    } else {
+        Ok(sections.join("\n").trim().to_string())
+    }
+}
+
+pub async fn handle_tldr(req: TldrRequest) -> CallToolResult {
+    let command = req.command.trim().to_lowercase();
+    if command.is_empty()
+        || command.starts_with('-')
+        || !command
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_.+".contains(c))
+    {
+        return CallToolResult::success(vec![Content::text(format!(
+            "Error: invalid command '{}'",
+            req.command
+        ))]);
+    }
+
+    let cache_path = get_tldr_cache_dir().join(format!("{}.md", command));
+    let is_fresh = fs::metadata(&cache_path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|modified| modified.elapsed().ok())
+        .is_some_and(|age| age < TLDR_CACHE_TTL);
+    if is_fresh && let Ok(page) = fs::read_to_string(&cache_path) {
+        return CallToolResult::success(vec![Content::text(page)]);
+    }
+
+    let page = if find_in_path("tldr").is_some() {
+        tldr_from_client(&command).await
+    } else {
+        tldr_from_github(&command).await
+    };
+
+    match page {
+        Ok(page) => {
+            if let Some(parent) = cache_path.parent() {
+                let _ = fs::create_dir_all(parent);
+            }
+            let _ = fs::write(&cache_path, &page);
+            CallToolResult::success(vec![Content::text(page)])
+        }
+        Err(e) => CallToolResult::success(vec![Content::text(e)]),
+    }
+}
+
+async fn tldr_from_client(command: &str) -> Result<String, String> {
+    let output = Command::new("tldr")
+        .arg(command)
+        .env("NO_COLOR", "1")
+        .output()
+        .await
+        .map_err(|e| format!("Failed to run tldr: {}", e))?;
+    if output.status.success() {
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        Err(format!("tldr error: {}", stderr.trim()))
+    }
+}
+
+async fn tldr_from_github(command: &str) -> Result<String, String> {
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(10))
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+
+    for platform in ["common", "linux"] {
+        let url = format!(
+            "https://raw.githubusercontent.com/tldr-pages/tldr/main/pages/{}/{}.md",
+            platform, command
+        );
+        let response = client
+            .get(&url)
+            .send()
+            .await
+            .map_err(|e| format!("Failed to fetch {}: {}", url, e))?;
+        if response.status().is_success() {
+            return response
+                .text()
+                .await
+                .map(|page| page.trim().to_string())
+                .map_err(|e| format!("Failed to read {}: {}", url, e));
+        }
+    }
+    Err(format!("No tldr page found for '{}'", command))
+}
+
+fn get_tldr_cache_dir() -> PathBuf {
+    dirs::home_dir()
+        .map(|h| h.join(".cache/steve/tldr"))
+        .unwrap_or_else(|| PathBuf::from(".tldr"))
+}
+
+pub async fn handle_timer(
+    req: TimerRequest,
+    stopwatches: &Mutex<HashMap<String, Instant>>,
+) -> CallToolResult {
+    const MAX_TIMER_SECS: u64 = 3600;
+
+    let label = req.label.as_deref().unwrap_or("default").to_string();
+    let result = match req.action.as_str() {
+        "start" => {
+            let mut stopwatches = stopwatches.lock().unwrap_or_else(|e| e.into_inner());
+            if stopwatches.contains_key(&label) {
+                Err(format!("Stopwatch '{}' is already running", label))
+            } else {
+                stopwatches.insert(label.clone(), Instant::now());
+                Ok(format!("⏱ Started stopwatch '{}'", label))
+            }
+        }
+        "stop" => stopwatches
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(&label)
+            .map(|started| {
+                format!(
+                    "⏱ Stopped '{}' after {}",
+                    label,
+                    format_elapsed(started.elapsed())
+                )
+            })
+            .ok_or_else(|| format!("No stopwatch named '{}'", label)),
+        "status" => {
+            let stopwatches = stopwatches.lock().unwrap_or_else(|e| e.into_inner());
+            let mut running: Vec<String> = stopwatches
+                .iter()
+                .map(|(label, started)| format!("{}: {}", label, format_elapsed(started.elapsed())))
+                .collect();
+            running.sort();
+            if running.is_empty() {
+                Ok("No stopwatches running".to_string())
+            } else {
+                Ok(running.join("\n"))
+            }
+        }
+        "timer" => match req.duration_secs {
+            Some(secs) if secs <= MAX_TIMER_SECS => {
+                tokio::time::sleep(Duration::from_secs(secs)).await;
+                Ok(format!("⏰ Timer '{}' finished after {}s", label, secs))
+            }
+            Some(_) => Err(format!(
+                "Error: 'duration_secs' must be at most {}",
+                MAX_TIMER_SECS
+            )),
+            None => Err("Error: 'duration_secs' is required for timer action".to_string()),
+        },
+        _ => Err(format!(
+            "Unknown action '{}'. Use: start, stop, status, timer",
+            req.action
+        )),
+    };
+
+    match result {
+        Ok(output) => CallToolResult::success(vec![Content::text(output)]),
+        Err(e) => CallToolResult::success(vec![Content::text(e)]),
+    }
+}
+
+fn format_elapsed(elapsed: Duration) -> String {
+    let secs = elapsed.as_secs();
+    format!(
+        "{:02}:{:02}:{:02}.{:01}",
+        secs / 3600,
+        (secs / 60) % 60,
+        secs % 60,
+        elapsed.subsec_millis() / 100
+    )
+}
+
+pub fn handle_word_count(req: WordCountRequest) -> CallToolResult {
+    let result = match (req.text.as_deref(), req.file.as_deref()) {
+        (Some(text), None) => {
+            let mut counts = TextCounts::default();
+            for line in text.split_inclusive('\n') {
+                counts.add_line(line.as_bytes());
+            }
+            Ok((counts, "text".to_string()))
+        }
+        (None, Some(file)) => {
+            count_file(&expand_path(file)).map(|counts| (counts, file.to_string()))
+        }
+        _ => Err("Error: provide exactly one of 'text' or 'file'".to_string()),
+    };
+
+    let text = match result {
+        Ok((counts, name)) => format!(
+            "{:>8} {:>8} {:>8} {:>8}\n{:>8} {:>8} {:>8} {:>8}  {}",
+            "Lines",
+            "Words",
+            "Chars",
+            "Bytes",
+            counts.lines,
+            counts.words,
+            counts.chars,
+            counts.bytes,
+            name
+        ),
+        Err(e) => e,
+    };
+    CallToolResult::success(vec![Content::text(text)])
+}
+
+#[derive(Default)]
+struct TextCounts {
+    lines: usize,
+    words: usize,
+    chars: usize,
+    bytes: usize,
+}
+
+impl TextCounts {
+    fn add_line(&mut self, line: &[u8]) {
+        let text = String::from_utf8_lossy(line);
+        self.lines += 1;
+        self.words += text.split_whitespace().count();
+        self.chars += text.chars().count();
+        self.bytes += line.len();
+    }
+}
+
+/// Count a file line by line, so large files are never read into memory whole.
+fn count_file(path: &Path) -> Result<TextCounts, String> {
+    let file = File::open(path).map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+    let mut reader = BufReader::new(file);
+    let mut counts = TextCounts::default();
+    let mut line = Vec::new();
+    loop {
+        line.clear();
+        match reader.read_until(b'\n', &mut line) {
+            Ok(0) => return Ok(counts),
+            Ok(_) => counts.add_line(&line),
+            Err(e) => return Err(format!("Failed to read {}: {}", path.display(), e)),
+        }
+    }
+}
+
+pub fn handle_diff_text(req: DiffRequest) -> CallToolResult {
+    if req.old_text == req.new_text {
+        return CallToolResult::success(vec![Content::text("No differences".to_string())]);
+    }
+
+    let diff = TextDiff::from_lines(&req.old_text, &req.new_text);
+    let unified = diff
+        .unified_diff()
+        .context_radius(req.context_lines.unwrap_or(3))
+        .header(
+            req.old_label.as_deref().unwrap_or("old"),
+            req.new_label.as_deref().unwrap_or("new"),
+        )
+        .to_string();
+    CallToolResult::success(vec![Content::text(unified)])
+}
+
+pub fn handle_template_render(req: TemplateRequest) -> CallToolResult {
+    let text = match render_template(&req.template, &req.variables) {
+        Ok(rendered) => rendered,
+        Err(e) => e,
+    };
+    CallToolResult::success(vec![Content::text(text)])
+}
+
+/// Replace each `{{ name }}` with its value, failing if any name is missing.
+fn render_template(template: &str, variables: &HashMap<String, String>) -> Result<String, String> {
+    let mut rendered = String::with_capacity(template.len());
+    let mut missing: Vec<&str> = Vec::new();
+    let mut rest = template;
+
+    while let Some(start) = rest.find("{{") {
+        rendered.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let end = after.find("}}").ok_or_else(|| {
+            format!(
+                "Error: unclosed '{{{{' at byte {}",
+                template.len() - rest.len() + start
+            )
+        })?;
+        let name = after[..end].trim();
+        match variables.get(name) {
+            Some(value) => rendered.push_str(value),
+            None => {
+                if !missing.contains(&name) {
+                    missing.push(name);
+                }
+            }
+        }
+        rest = &after[end + 2..];
+    }
+    rendered.push_str(rest);
+
+    if missing.is_empty() {
+        Ok(rendered)
+    } else {
+        Err(format!("Error: missing variables: {}", missing.join(", ")))
+    }
+}